@@ -0,0 +1,105 @@
+// This Source Code Form is subject to the terms of the Mozilla Public License,
+// v. 2.0. If a copy of the MPL was not distributed with this file, You can
+// obtain one at https://mozilla.org/MPL/2.0/.
+//! Audits a license inventory: one key per line on standard input, e.g. a
+//! column exported from a spreadsheet. Prints each invalid key (redacted, so
+//! the output can be shared), then a summary by format and by error.
+//!
+//! `--year YYYY` only accepts formats released by then, for inventories
+//! known to date from a given year, and `--suggest` lists the one-digit fixes
+//! for keys that fail the checksum.
+//!
+//! ```text
+//! cargo run --example audit -- --suggest < keys.txt
+//! ```
+
+use std::{collections::HashMap,
+          env,
+          io::{self, BufRead},
+          process};
+
+use wkv::{FormatRegistry, RedactionStyle, WKVError};
+
+fn main()
+{
+  let mut registry = FormatRegistry::new();
+  let mut suggest = false;
+  let mut args = env::args().skip(1);
+
+  while let Some(arg) = args.next() {
+    match arg.as_str() {
+      "--suggest" => suggest = true,
+      "--year" => {
+        let year = args.next().and_then(|x| x.parse().ok()).unwrap_or_else(|| {
+                                                              usage("--year needs a year")
+                                                            });
+
+        registry = FormatRegistry::empty();
+        for format in wkv::formats_in_use(year) {
+          registry.register(format);
+        }
+      },
+      x => usage(&format!("unknown argument {x}")),
+    }
+  }
+
+  let lines = match io::stdin().lock().lines().collect::<io::Result<Vec<_>>>() {
+    Ok(x) => x,
+    Err(e) => {
+      eprintln!("audit: {e}");
+      process::exit(2);
+    },
+  };
+  let mut statistics = wkv::BatchStatistics::default();
+
+  for (n, line) in lines.iter().enumerate() {
+    let key = line.trim();
+
+    if key.is_empty() {
+      continue;
+    }
+
+    let result = registry.validate(key);
+
+    if let Err(e) = &result {
+      println!("line {}: {}: {e}", n + 1, wkv::redact(key, RedactionStyle::KeepFirstSegment));
+      if suggest && *e == WKVError::BadMod7 {
+        if let Err((_, suggestions)) = wkv::validate_with_suggestions(key) {
+          for x in suggestions {
+            println!("  maybe character {} should be {}", x.offset + 1, char::from(x.replacement));
+          }
+        }
+      }
+    }
+    statistics.record(&result);
+  }
+
+  let valid: usize = statistics.by_type.values().sum();
+  let invalid: usize = statistics.by_error.values().sum();
+
+  println!("\n{} keys: {valid} valid, {invalid} invalid", valid + invalid);
+  for (format, count) in sorted(&statistics.by_type, |x| x.name().to_owned()) {
+    println!("  {format}: {count}");
+  }
+  for (error, count) in sorted(&statistics.by_error, WKVError::to_string) {
+    println!("  {error}: {count}");
+  }
+
+  process::exit(i32::from(invalid > 0));
+}
+
+/// The entries of `counts` with their keys written out by `label`, most
+/// common first, and alphabetically among equals so the output is stable.
+fn sorted<K>(counts: &HashMap<K, usize>, label: impl Fn(&K) -> String) -> Vec<(String, usize)>
+{
+  let mut counts: Vec<_> = counts.iter().map(|(k, &v)| (label(k), v)).collect();
+
+  counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+  counts
+}
+
+fn usage(problem: &str) -> !
+{
+  eprintln!("audit: {problem}\nusage: audit [--suggest] [--year YYYY] < KEYS");
+  process::exit(2);
+}
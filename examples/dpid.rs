@@ -0,0 +1,78 @@
+// This Source Code Form is subject to the terms of the Mozilla Public License,
+// v. 2.0. If a copy of the MPL was not distributed with this file, You can
+// obtain one at https://mozilla.org/MPL/2.0/.
+//! Recovers the product key from a `DigitalProductId` registry value, given
+//! in hex as `reg query` prints it:
+//!
+//! ```text
+//! reg query "HKLM\SOFTWARE\Microsoft\Windows NT\CurrentVersion" /v DigitalProductId
+//! cargo run --example dpid -- A40000000300000030...
+//! ```
+//!
+//! With no argument, the hex is read from standard input, so a whole
+//! `reg query` line can be pasted. Spaces, commas and a `hex:` prefix (as in
+//! `.reg` exports) are ignored. The key is redacted unless `--show` is given.
+
+use std::{env,
+          io::{self, Read},
+          process};
+
+fn main()
+{
+  let mut show = false;
+  let mut hex = String::new();
+
+  for arg in env::args().skip(1) {
+    if arg == "--show" {
+      show = true;
+    } else {
+      hex.push_str(&arg);
+    }
+  }
+  if hex.is_empty() {
+    if let Err(e) = io::stdin().read_to_string(&mut hex) {
+      fail(&e.to_string());
+    }
+  }
+
+  let blob = parse_hex(&hex).unwrap_or_else(|| fail("expected an even number of hex digits"));
+  let key = wkv::decode_digital_product_id(&blob).unwrap_or_else(|e| fail(&e.to_string()));
+  let formats: Vec<_> = wkv::identify(&key).matches
+                                           .iter()
+                                           .map(|x| x.release.name())
+                                           .collect();
+
+  if show {
+    println!("{key}");
+  } else {
+    println!("{}", wkv::redact(&key, wkv::RedactionStyle::KeepLastSegment));
+  }
+  println!("valid for: {}", formats.join(", "));
+}
+
+/// The bytes written as hex in `text`. Words that aren't hex, like
+/// `REG_BINARY`, are skipped, as are the `\` that continue lines in `.reg`
+/// files.
+fn parse_hex(text: &str) -> Option<Vec<u8>>
+{
+  let digits: Vec<u8> = text.split_whitespace()
+                            .map(|x| x.trim_start_matches("hex:").trim_end_matches('\\'))
+                            .filter(|x| x.chars().all(|c| c.is_ascii_hexdigit() || c == ','))
+                            .flat_map(str::bytes)
+                            .filter(u8::is_ascii_hexdigit)
+                            .collect();
+
+  if digits.len() % 2 != 0 {
+    return None;
+  }
+
+  digits.chunks(2)
+        .map(|x| u8::from_str_radix(std::str::from_utf8(x).ok()?, 16).ok())
+        .collect()
+}
+
+fn fail(problem: &str) -> !
+{
+  eprintln!("dpid: {problem}");
+  process::exit(1);
+}
@@ -0,0 +1,113 @@
+// This Source Code Form is subject to the terms of the Mozilla Public License,
+// v. 2.0. If a copy of the MPL was not distributed with this file, You can
+// obtain one at https://mozilla.org/MPL/2.0/.
+//! Scans files for product keys, e.g. an old share full of install notes and
+//! setup logs. Directories are scanned recursively. Each key found is printed
+//! with where it was found and every format it's valid for, redacted unless
+//! `--show` is given. Progress goes to standard error.
+//!
+//! ```text
+//! cargo run --example scan -- /mnt/archive
+//! ```
+//!
+//! Like the rest of wkv, this only reads: nothing is written to disk.
+
+use std::{env,
+          fs,
+          io::{self, Write},
+          path::{Path, PathBuf},
+          process};
+
+/// The characters that can be part of a key. Anything else ends one.
+fn in_key(c: char) -> bool
+{
+  c.is_ascii_alphanumeric() || c == '-'
+}
+
+fn main()
+{
+  let mut show = false;
+  let mut roots = Vec::new();
+
+  for arg in env::args().skip(1) {
+    if arg == "--show" {
+      show = true;
+    } else {
+      roots.push(PathBuf::from(arg));
+    }
+  }
+  if roots.is_empty() {
+    eprintln!("usage: scan [--show] PATH...");
+    process::exit(2);
+  }
+
+  let mut files = Vec::new();
+
+  for root in &roots {
+    if let Err(e) = collect(root, &mut files) {
+      eprintln!("scan: {}: {e}", root.display());
+    }
+  }
+
+  let mut hits = 0;
+
+  for (i, path) in files.iter().enumerate() {
+    eprint!("\r\x1b[K[{}/{}] {}", i + 1, files.len(), path.display());
+    let _ = io::stderr().flush();
+
+    let text = match fs::read(path) {
+      Ok(x) => String::from_utf8_lossy(&x).into_owned(),
+      Err(e) => {
+        eprintln!("\nscan: {}: {e}", path.display());
+        continue;
+      },
+    };
+
+    for (n, line) in text.lines().enumerate() {
+      for candidate in line.split(|c| !in_key(c)).filter(|x| wkv::shape_of(x).is_some()) {
+        let matches = wkv::identify(candidate).matches;
+        let key = match matches.first() {
+          Some(_) if show => candidate.to_owned(),
+          Some(x) => x.redacted(),
+          None => continue,
+        };
+        let formats: Vec<_> = matches.iter().map(|x| x.release.name()).collect();
+
+        hits += 1;
+        eprint!("\r\x1b[K");
+        println!("{}:{}: {key} ({})", path.display(), n + 1, formats.join(", "));
+      }
+    }
+  }
+
+  eprintln!("\r\x1b[K{hits} keys in {} files", files.len());
+}
+
+/// Adds `path` to `files` if it's a file, or every file under it if it's a
+/// directory, in a stable order. Symbolic links to directories are skipped
+/// below `path`, since following them could loop forever.
+fn collect(path: &Path, files: &mut Vec<PathBuf>) -> io::Result<()>
+{
+  if !fs::metadata(path)?.is_dir() {
+    files.push(path.to_owned());
+    return Ok(());
+  }
+
+  let mut entries = fs::read_dir(path)?.map(|x| x.map(|x| x.path()))
+                                       .collect::<io::Result<Vec<_>>>()?;
+
+  entries.sort();
+  for entry in entries {
+    let result = match fs::symlink_metadata(&entry) {
+      Ok(x) if x.file_type().is_symlink() && entry.is_dir() => continue,
+      Ok(_) => collect(&entry, files),
+      Err(e) => Err(e),
+    };
+
+    if let Err(e) = result {
+      eprintln!("scan: {}: {e}", entry.display());
+    }
+  }
+
+  Ok(())
+}
@@ -0,0 +1,125 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public License,
+ * v. 2.0. If a copy of the MPL was not distributed with this file, You can
+ * obtain one at https://mozilla.org/MPL/2.0/. */
+
+#ifndef WKV_H
+#define WKV_H
+
+/* Generated by cbindgen from src/ffi.rs. Don't edit by hand. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// The key is valid.
+#define WKV_OK 0
+
+// [`WKVError::TooShort`](../enum.WKVError.html#variant.TooShort).
+#define WKV_TOO_SHORT 1
+
+// [`WKVError::TooLong`](../enum.WKVError.html#variant.TooLong).
+#define WKV_TOO_LONG 2
+
+// [`WKVError::BadMod7`](../enum.WKVError.html#variant.BadMod7).
+#define WKV_BAD_MOD7 3
+
+// [`WKVError::ExpectedDigit`](../enum.WKVError.html#variant.ExpectedDigit).
+#define WKV_EXPECTED_DIGIT 4
+
+// [`WKVError::InvalidDigitPosition`](../enum.WKVError.html#variant.InvalidDigitPosition).
+#define WKV_INVALID_DIGIT_POSITION 5
+
+// [`WKVError::ExpectedSeparator`](../enum.WKVError.html#variant.ExpectedSeparator).
+#define WKV_EXPECTED_SEPARATOR 6
+
+// [`WKVError::ExpectedOEM`](../enum.WKVError.html#variant.ExpectedOEM).
+#define WKV_EXPECTED_OEM 7
+
+// [`WKVError::InvalidCharacter`](../enum.WKVError.html#variant.InvalidCharacter).
+#define WKV_INVALID_CHARACTER 8
+
+// [`WKVError::DecodedTooLarge`](../enum.WKVError.html#variant.DecodedTooLarge).
+#define WKV_DECODED_TOO_LARGE 9
+
+// [`WKVError::InvalidDate`](../enum.WKVError.html#variant.InvalidDate).
+#define WKV_INVALID_DATE 10
+
+// [`WKVError::BadAccess`](../enum.WKVError.html#variant.BadAccess), which is
+// no longer returned.
+#define WKV_BAD_ACCESS 11
+
+// [`WKVError::InsufficientConfidence`](../enum.WKVError.html#variant.InsufficientConfidence).
+#define WKV_INSUFFICIENT_CONFIDENCE 12
+
+// [`WKVError::QuirkOnlyValid`](../enum.WKVError.html#variant.QuirkOnlyValid).
+#define WKV_QUIRK_ONLY_VALID 13
+
+// [`WKVError::NonAscii`](../enum.WKVError.html#variant.NonAscii).
+#define WKV_NON_ASCII 14
+
+// [`WKVError::Internal`](../enum.WKVError.html#variant.Internal).
+#define WKV_INTERNAL 15
+
+// [`WKVError::UnknownFormat`](../enum.WKVError.html#variant.UnknownFormat).
+#define WKV_UNKNOWN_FORMAT 16
+
+// The key was a null pointer or wasn't UTF-8. There's no Rust equivalent.
+#define WKV_INVALID_ARGUMENT -1
+
+// [`KeyType::Windows95`](../enum.KeyType.html#variant.Windows95).
+#define WKV_TYPE_WINDOWS95 0
+
+// [`KeyType::Windows95OEM`](../enum.KeyType.html#variant.Windows95OEM).
+#define WKV_TYPE_WINDOWS95_OEM 1
+
+// [`KeyType::Windows98`](../enum.KeyType.html#variant.Windows98).
+#define WKV_TYPE_WINDOWS98 2
+
+// [`KeyType::WindowsNT4`](../enum.KeyType.html#variant.WindowsNT4).
+#define WKV_TYPE_WINDOWS_NT4 3
+
+// [`KeyType::Office97`](../enum.KeyType.html#variant.Office97).
+#define WKV_TYPE_OFFICE97 4
+
+// [`KeyType::Unknown`](../enum.KeyType.html#variant.Unknown).
+#define WKV_TYPE_UNKNOWN 5
+
+// [`KeyType::Windows2000`](../enum.KeyType.html#variant.Windows2000).
+#define WKV_TYPE_WINDOWS2000 6
+
+// [`KeyType::WindowsXP`](../enum.KeyType.html#variant.WindowsXP).
+#define WKV_TYPE_WINDOWS_XP 7
+
+// Validates the NUL-terminated `key` like [`validate`](../fn.validate.html).
+// On success, the key's type is written to `key_type` unless it's null.
+//
+// # Safety
+//
+// `key` must be null or point to a NUL-terminated string, and `key_type`
+// must be null or valid for writes.
+int wkv_validate(const char *key, int *key_type);
+
+// Writes the type of every format the NUL-terminated `key` is valid for to
+// `key_types`, like [`identify`](../fn.identify.html), stopping after `len`.
+// Returns how many formats matched, which may be more than `len`, or
+// `WKV_INVALID_ARGUMENT`.
+//
+// # Safety
+//
+// `key` must be null or point to a NUL-terminated string, and `key_types`
+// must be valid for `len` writes (or null, if `len` is 0).
+int wkv_identify(const char *key, int *key_types, size_t len);
+
+// Writes a description of the error `code` to `buffer` as a NUL-terminated
+// string, truncating it to fit in `len` bytes. Returns the length of the
+// whole description, excluding the NUL, so a return value of `len` or more
+// means it was truncated. Unknown codes are described as such.
+//
+// # Safety
+//
+// `buffer` must be valid for `len` writes (or null, if `len` is 0).
+size_t wkv_error_message(int code, char *buffer, size_t len);
+
+#endif  /* WKV_H */
@@ -0,0 +1,144 @@
+// This Source Code Form is subject to the terms of the Mozilla Public License,
+// v. 2.0. If a copy of the MPL was not distributed with this file, You can
+// obtain one at https://mozilla.org/MPL/2.0/.
+//! Statistical analysis of collections of keys.
+//!
+//! Naive keygens tend to give themselves away in bulk: they favour some
+//! digits over others, or stamp every key with the same site number. None of
+//! this proves anything about a single key, so these functions only work on
+//! whole corpora, and report their evidence alongside the verdict.
+
+use std::collections::HashMap;
+
+use crate::{Key, KeyParts};
+
+/// The chi-squared value above which a digit distribution is considered
+/// biased: the critical value for 9 degrees of freedom at p = 0.001.
+const CHI_SQUARED_CRITICAL: f64 = 27.88;
+
+/// The share of keys that may carry the same prefix before a corpus is
+/// considered stamped from a template.
+const PREFIX_SHARE_CRITICAL: f64 = 0.5;
+
+/// The fewest keys a prefix share is judged on. Below this, a few legitimate
+/// keys from the same batch would look suspicious.
+const PREFIX_MIN_KEYS: usize = 20;
+
+/// The evidence gathered by [`analyze`](fn.analyze.html).
+#[derive(Clone, Debug, PartialEq)]
+pub struct CorpusAnalysis
+{
+  /// How many keys had a decimal serial and were analyzed.
+  pub keys:              usize,
+  /// Pearson's chi-squared statistic for the serials' digits against a
+  /// uniform distribution, with 9 degrees of freedom.
+  pub digit_chi_squared: f64,
+  /// The most common prefix (site number, or OEM date) and the share of keys
+  /// that have it, from 0 to 1.
+  pub top_prefix:        (String, f64),
+  /// Whether the evidence points at a naive keygen: either the digits are
+  /// biased at p < 0.001, or more than half of at least 20 keys share a
+  /// prefix.
+  pub likely_generated:  bool,
+}
+
+/// Estimates whether `keys` were made by a naive keygen. Keys without a
+/// decimal serial (25-character keys) are skipped; returns `None` if that
+/// leaves nothing to analyze.
+#[inline]
+#[must_use]
+#[allow(clippy::as_conversions, clippy::cast_precision_loss, clippy::float_arithmetic)]
+pub fn analyze(keys: &[Key]) -> Option<CorpusAnalysis>
+{
+  let mut digit_counts = [0_u64; 10];
+  let mut prefixes: HashMap<String, usize> = HashMap::new();
+  let mut analyzed = 0_usize;
+
+  for key in keys {
+    if let (Some(metrics), Some(prefix)) = (key.metrics(), prefix(key)) {
+      for (total, &count) in digit_counts.iter_mut().zip(&metrics.digit_counts) {
+        *total += u64::from(count);
+      }
+      *prefixes.entry(prefix).or_insert(0) += 1;
+      analyzed += 1;
+    }
+  }
+
+  if analyzed == 0 {
+    return None;
+  }
+
+  let expected = digit_counts.iter().sum::<u64>() as f64 / 10.0;
+  let digit_chi_squared = digit_counts.iter()
+                                      .map(|&x| (x as f64 - expected).powi(2) / expected)
+                                      .sum::<f64>();
+  let (prefix, count) = prefixes.into_iter()
+                                .max_by(|a, b| a.1.cmp(&b.1).then_with(|| b.0.cmp(&a.0)))?;
+  let share = count as f64 / analyzed as f64;
+
+  Some(CorpusAnalysis { keys: analyzed,
+                        digit_chi_squared,
+                        top_prefix: (prefix, share),
+                        likely_generated: digit_chi_squared > CHI_SQUARED_CRITICAL
+                                          || (analyzed >= PREFIX_MIN_KEYS
+                                              && share > PREFIX_SHARE_CRITICAL), })
+}
+
+/// The part of `key` a template-based keygen would keep fixed.
+fn prefix(key: &Key) -> Option<String>
+{
+  match key.parts {
+    KeyParts::ThreeSeven { site, .. } => Some(String::from_utf8_lossy(&site).into_owned()),
+    KeyParts::FourSeven { site, .. } => Some(String::from_utf8_lossy(&site).into_owned()),
+    KeyParts::Oem { day, year, .. } => Some(format!("{day:03}{year:02}")),
+    KeyParts::Base24(_) => None,
+  }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests
+{
+  use super::*;
+  use crate::KeyType;
+
+  fn key(site: [u8; 3], serial: u32) -> Key
+  {
+    Key { release: KeyType::Windows95,
+          parts:   KeyParts::ThreeSeven { site, serial }, }
+  }
+
+  #[test]
+  fn template()
+  {
+    let keys: Vec<Key> = vec![key(*b"111", 7); 50];
+    let analysis = analyze(&keys).unwrap();
+    assert!(analysis.likely_generated);
+    assert_eq!(analysis.top_prefix, ("111".to_owned(), 1.0));
+  }
+
+  #[test]
+  fn spread()
+  {
+    // Walk through serials with a step coprime to 10^7, so every digit turns
+    // up about equally often, and spread the sites too.
+    let keys: Vec<Key> = (0..1000_u32).map(|i| {
+                                        let site = format!("{:03}", i % 300);
+                                        let mut bytes = [0; 3];
+                                        bytes.copy_from_slice(site.as_bytes());
+                                        key(bytes, i * 1_234_567 % 10_000_000)
+                                      })
+                                      .collect();
+    let analysis = analyze(&keys).unwrap();
+    assert!(!analysis.likely_generated, "{:?}", analysis);
+  }
+
+  #[test]
+  fn nothing()
+  {
+    assert_eq!(analyze(&[]), None);
+    assert_eq!(analyze(&[Key { release: KeyType::Windows98,
+                               parts:   KeyParts::Base24(0), }]),
+               None);
+  }
+}
@@ -0,0 +1,170 @@
+// This Source Code Form is subject to the terms of the Mozilla Public License,
+// v. 2.0. If a copy of the MPL was not distributed with this file, You can
+// obtain one at https://mozilla.org/MPL/2.0/.
+//! Extended Product ID (EPID) parsing.
+//!
+//! EPIDs show up in KMS host and client logs in the form
+//! `XXXXX-XXXXX-XXX-XXXXXX-XX-XXXX-XXXX.XXXX-XXXXXXX`, e.g.
+//! `05426-00206-271-298329-03-1033-9200.0000-0452012`.
+
+use crate::{number, WKVError};
+
+/// The length of an EPID, separators included.
+const EPID_LEN: usize = 48;
+
+/// Where each separator in an EPID must be, and what it must be.
+const SEPARATORS: [(usize, u8); 8] = [(5, b'-'),
+                                      (11, b'-'),
+                                      (15, b'-'),
+                                      (22, b'-'),
+                                      (25, b'-'),
+                                      (30, b'-'),
+                                      (35, b'.'),
+                                      (40, b'-')];
+
+/// A parsed extended Product ID.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ExtendedPid
+{
+  /// The OS platform ID of the product that issued the EPID.
+  pub platform:        u32,
+  /// The product group ID.
+  pub group:           u32,
+  /// The channel ID part of the product key ID.
+  pub channel_id:      u32,
+  /// The sequence part of the product key ID.
+  pub sequence:        u32,
+  /// The license channel. `03` is volume licensing.
+  pub license_channel: u32,
+  /// The Windows language ID (LCID), e.g. `1033` for en-US.
+  pub language:        u32,
+  /// The OS build number.
+  pub build:           u32,
+  /// The number after the build's `.`.
+  pub build_minor:     u32,
+  /// The day of the year (1–366) the license state was recorded.
+  pub activation_day:  u32,
+  /// The year the license state was recorded, 1995 to 2099.
+  pub activation_year: u32,
+}
+
+/// Parses an extended Product ID. Returns an
+/// [`ExtendedPid`](struct.ExtendedPid.html)
+///
+/// Every field must be made of digits. The activation date must be a real
+/// day of the year between 1995, when product IDs were introduced, and 2099.
+/// The other fields are IDs whose valid values Microsoft doesn't publish, so
+/// any number is accepted for them.
+#[inline]
+pub fn parse(epid: &'_ str) -> Result<ExtendedPid, WKVError>
+{
+  let epid = epid.as_bytes();
+
+  match epid.len() {
+    x if x < EPID_LEN => return Err(WKVError::TooShort),
+    x if x > EPID_LEN => return Err(WKVError::TooLong),
+    _ => {},
+  }
+
+  for &(i, separator) in &SEPARATORS {
+    if *epid.get(i).ok_or(WKVError::Internal)? != separator {
+      return Err(WKVError::ExpectedSeparator);
+    }
+  }
+
+  let pid = ExtendedPid { platform:        field(epid, 0, 5)?,
+                          group:           field(epid, 6, 11)?,
+                          channel_id:      field(epid, 12, 15)?,
+                          sequence:        field(epid, 16, 22)?,
+                          license_channel: field(epid, 23, 25)?,
+                          language:        field(epid, 26, 30)?,
+                          build:           field(epid, 31, 35)?,
+                          build_minor:     field(epid, 36, 40)?,
+                          activation_day:  field(epid, 41, 44)?,
+                          activation_year: field(epid, 44, 48)?, };
+
+  if (1..=366).contains(&pid.activation_day) && (1995..=2099).contains(&pid.activation_year) {
+    Ok(pid)
+  } else {
+    Err(WKVError::InvalidDate)
+  }
+}
+
+/// Reads `epid[start..end]` as a decimal number.
+fn field(epid: &[u8], start: usize, end: usize) -> Result<u32, WKVError>
+{
+  number(epid.get(start..end).ok_or(WKVError::Internal)?)
+}
+
+#[cfg(test)]
+mod tests
+{
+  use super::*;
+
+  #[test]
+  fn kms()
+  {
+    assert_eq!(parse("05426-00206-271-298329-03-1033-9200.0000-0452012"),
+               Ok(ExtendedPid { platform:        5426,
+                                group:           206,
+                                channel_id:      271,
+                                sequence:        298_329,
+                                license_channel: 3,
+                                language:        1033,
+                                build:           9200,
+                                build_minor:     0,
+                                activation_day:  45,
+                                activation_year: 2012, }));
+  }
+
+  #[test]
+  fn bad_separator()
+  {
+    assert_eq!(parse("05426-00206-271-298329-03-1033-9200-0000-0452012"),
+               Err(WKVError::ExpectedSeparator));
+  }
+
+  #[test]
+  fn bad_day()
+  {
+    assert_eq!(parse("05426-00206-271-298329-03-1033-9200.0000-3672012"),
+               Err(WKVError::InvalidDate));
+    assert_eq!(parse("05426-00206-271-298329-03-1033-9200.0000-0002012"),
+               Err(WKVError::InvalidDate));
+  }
+
+  #[test]
+  fn bad_year()
+  {
+    assert_eq!(parse("05426-00206-271-298329-03-1033-9200.0000-0451994"),
+               Err(WKVError::InvalidDate));
+    assert_eq!(parse("05426-00206-271-298329-03-1033-9200.0000-0459999"),
+               Err(WKVError::InvalidDate));
+    assert_eq!(parse("05426-00206-271-298329-03-1033-9200.0000-0452099").map(|x| x.activation_year),
+               Ok(2099));
+  }
+
+  #[test]
+  fn non_digit()
+  {
+    const EPID: &[u8; EPID_LEN] = b"05426-00206-271-298329-03-1033-9200.0000-0452012";
+
+    // The first character of each field.
+    for i in [0, 6, 12, 16, 23, 26, 31, 36, 41, 44] {
+      let mut epid = *EPID;
+      if let Some(x) = epid.get_mut(i) {
+        *x = b'X';
+      }
+      assert_eq!(core::str::from_utf8(&epid).map(parse),
+                 Ok(Err(WKVError::ExpectedDigit)),
+                 "field at {i}");
+    }
+  }
+
+  #[test]
+  fn short()
+  {
+    assert_eq!(parse("05426-00206-271-298329-03-1033"),
+               Err(WKVError::TooShort));
+  }
+}
@@ -0,0 +1,321 @@
+// This Source Code Form is subject to the terms of the Mozilla Public License,
+// v. 2.0. If a copy of the MPL was not distributed with this file, You can
+// obtain one at https://mozilla.org/MPL/2.0/.
+//! A C interface, for calling wkv without a Rust toolchain.
+//!
+//! Build a shared library exporting these functions with
+//! `cargo rustc --lib --release --features ffi --crate-type cdylib`. The
+//! matching header is `include/wkv.h`, generated with
+//! `cbindgen --config cbindgen.toml --output include/wkv.h`.
+//!
+//! Functions return `WKV_OK` (0) or one of the error codes below, mirroring
+//! [`WKVError`](../enum.WKVError.html). Key types are reported as the
+//! `WKV_TYPE_` codes.
+#![warn(unsafe_op_in_unsafe_fn)]
+
+use core::{convert::TryFrom, slice};
+use std::{ffi::CStr,
+          os::raw::{c_char, c_int}};
+
+use crate::{KeyType, WKVError};
+
+/// The key is valid.
+pub const WKV_OK: c_int = 0;
+/// [`WKVError::TooShort`](../enum.WKVError.html#variant.TooShort).
+pub const WKV_TOO_SHORT: c_int = 1;
+/// [`WKVError::TooLong`](../enum.WKVError.html#variant.TooLong).
+pub const WKV_TOO_LONG: c_int = 2;
+/// [`WKVError::BadMod7`](../enum.WKVError.html#variant.BadMod7).
+pub const WKV_BAD_MOD7: c_int = 3;
+/// [`WKVError::ExpectedDigit`](../enum.WKVError.html#variant.ExpectedDigit).
+pub const WKV_EXPECTED_DIGIT: c_int = 4;
+/// [`WKVError::InvalidDigitPosition`](../enum.WKVError.html#variant.InvalidDigitPosition).
+pub const WKV_INVALID_DIGIT_POSITION: c_int = 5;
+/// [`WKVError::ExpectedSeparator`](../enum.WKVError.html#variant.ExpectedSeparator).
+pub const WKV_EXPECTED_SEPARATOR: c_int = 6;
+/// [`WKVError::ExpectedOEM`](../enum.WKVError.html#variant.ExpectedOEM).
+pub const WKV_EXPECTED_OEM: c_int = 7;
+/// [`WKVError::InvalidCharacter`](../enum.WKVError.html#variant.InvalidCharacter).
+pub const WKV_INVALID_CHARACTER: c_int = 8;
+/// [`WKVError::DecodedTooLarge`](../enum.WKVError.html#variant.DecodedTooLarge).
+pub const WKV_DECODED_TOO_LARGE: c_int = 9;
+/// [`WKVError::InvalidDate`](../enum.WKVError.html#variant.InvalidDate).
+pub const WKV_INVALID_DATE: c_int = 10;
+/// [`WKVError::BadAccess`](../enum.WKVError.html#variant.BadAccess), which is
+/// no longer returned.
+pub const WKV_BAD_ACCESS: c_int = 11;
+/// [`WKVError::InsufficientConfidence`](../enum.WKVError.html#variant.InsufficientConfidence).
+pub const WKV_INSUFFICIENT_CONFIDENCE: c_int = 12;
+/// [`WKVError::QuirkOnlyValid`](../enum.WKVError.html#variant.QuirkOnlyValid).
+pub const WKV_QUIRK_ONLY_VALID: c_int = 13;
+/// [`WKVError::NonAscii`](../enum.WKVError.html#variant.NonAscii).
+pub const WKV_NON_ASCII: c_int = 14;
+/// [`WKVError::Internal`](../enum.WKVError.html#variant.Internal).
+pub const WKV_INTERNAL: c_int = 15;
+/// [`WKVError::UnknownFormat`](../enum.WKVError.html#variant.UnknownFormat).
+pub const WKV_UNKNOWN_FORMAT: c_int = 16;
+/// The key was a null pointer or wasn't UTF-8. There's no Rust equivalent.
+pub const WKV_INVALID_ARGUMENT: c_int = -1;
+
+/// [`KeyType::Windows95`](../enum.KeyType.html#variant.Windows95).
+pub const WKV_TYPE_WINDOWS95: c_int = 0;
+/// [`KeyType::Windows95OEM`](../enum.KeyType.html#variant.Windows95OEM).
+pub const WKV_TYPE_WINDOWS95_OEM: c_int = 1;
+/// [`KeyType::Windows98`](../enum.KeyType.html#variant.Windows98).
+pub const WKV_TYPE_WINDOWS98: c_int = 2;
+/// [`KeyType::WindowsNT4`](../enum.KeyType.html#variant.WindowsNT4).
+pub const WKV_TYPE_WINDOWS_NT4: c_int = 3;
+/// [`KeyType::Office97`](../enum.KeyType.html#variant.Office97).
+pub const WKV_TYPE_OFFICE97: c_int = 4;
+/// [`KeyType::Unknown`](../enum.KeyType.html#variant.Unknown).
+pub const WKV_TYPE_UNKNOWN: c_int = 5;
+/// [`KeyType::Windows2000`](../enum.KeyType.html#variant.Windows2000).
+pub const WKV_TYPE_WINDOWS2000: c_int = 6;
+/// [`KeyType::WindowsXP`](../enum.KeyType.html#variant.WindowsXP).
+pub const WKV_TYPE_WINDOWS_XP: c_int = 7;
+
+/// Validates the NUL-terminated `key` like [`validate`](../fn.validate.html).
+/// On success, the key's type is written to `key_type` unless it's null.
+///
+/// # Safety
+///
+/// `key` must be null or point to a NUL-terminated string, and `key_type`
+/// must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn wkv_validate(key: *const c_char, key_type: *mut c_int) -> c_int
+{
+  // SAFETY: the caller promises `key` is null or NUL-terminated.
+  let key = match unsafe { string(key) } {
+    Some(x) => x,
+    None => return WKV_INVALID_ARGUMENT,
+  };
+
+  match crate::validate(key) {
+    Ok(x) => {
+      // SAFETY: the caller promises `key_type` is null or valid for writes.
+      if let Some(key_type) = unsafe { key_type.as_mut() } {
+        *key_type = type_code(x.release);
+      }
+      WKV_OK
+    },
+    Err(e) => error_code(&e),
+  }
+}
+
+/// Writes the type of every format the NUL-terminated `key` is valid for to
+/// `key_types`, like [`identify`](../fn.identify.html), stopping after `len`.
+/// Returns how many formats matched, which may be more than `len`, or
+/// `WKV_INVALID_ARGUMENT`.
+///
+/// # Safety
+///
+/// `key` must be null or point to a NUL-terminated string, and `key_types`
+/// must be valid for `len` writes (or null, if `len` is 0).
+#[no_mangle]
+pub unsafe extern "C" fn wkv_identify(key: *const c_char,
+                                      key_types: *mut c_int,
+                                      len: usize)
+                                      -> c_int
+{
+  // SAFETY: the caller promises `key` is null or NUL-terminated.
+  let key = match unsafe { string(key) } {
+    Some(x) => x,
+    None => return WKV_INVALID_ARGUMENT,
+  };
+  let matches = crate::identify(key).matches;
+
+  if len > 0 {
+    // SAFETY: the caller promises `key_types` has room for `len` values.
+    let key_types = unsafe { slice::from_raw_parts_mut(key_types, len) };
+
+    for (x, key) in key_types.iter_mut().zip(&matches) {
+      *x = type_code(key.release);
+    }
+  }
+
+  c_int::try_from(matches.len()).unwrap_or(c_int::MAX)
+}
+
+/// Writes a description of the error `code` to `buffer` as a NUL-terminated
+/// string, truncating it to fit in `len` bytes. Returns the length of the
+/// whole description, excluding the NUL, so a return value of `len` or more
+/// means it was truncated. Unknown codes are described as such.
+///
+/// # Safety
+///
+/// `buffer` must be valid for `len` writes (or null, if `len` is 0).
+#[no_mangle]
+pub unsafe extern "C" fn wkv_error_message(code: c_int, buffer: *mut c_char, len: usize) -> usize
+{
+  let message = error(code).map_or_else(|| match code {
+                                          WKV_OK => "no error".to_owned(),
+                                          WKV_INVALID_ARGUMENT => {
+                                            "key is null or not UTF-8".to_owned()
+                                          },
+                                          _ => "unknown error code".to_owned(),
+                                        },
+                                        |x| x.to_string());
+  let message = message.as_bytes();
+
+  if len > 0 {
+    // SAFETY: the caller promises `buffer` has room for `len` bytes.
+    let buffer = unsafe { slice::from_raw_parts_mut(buffer.cast::<u8>(), len) };
+    let copied = message.len().min(len - 1);
+
+    for (x, &c) in buffer.iter_mut().zip(message.get(..copied).unwrap_or_default()) {
+      *x = c;
+    }
+    if let Some(x) = buffer.get_mut(copied) {
+      *x = 0;
+    }
+  }
+
+  message.len()
+}
+
+/// Reads a NUL-terminated UTF-8 string.
+///
+/// # Safety
+///
+/// `x` must be null or point to a NUL-terminated string.
+unsafe fn string<'string>(x: *const c_char) -> Option<&'string str>
+{
+  if x.is_null() {
+    None
+  } else {
+    // SAFETY: `x` isn't null, and the caller promises it's NUL-terminated.
+    unsafe { CStr::from_ptr(x) }.to_str().ok()
+  }
+}
+
+/// The `WKV_TYPE_` code for `x`.
+const fn type_code(x: KeyType) -> c_int
+{
+  match x {
+    KeyType::Windows95 => WKV_TYPE_WINDOWS95,
+    KeyType::Windows95OEM => WKV_TYPE_WINDOWS95_OEM,
+    KeyType::Windows98 => WKV_TYPE_WINDOWS98,
+    KeyType::WindowsNT4 => WKV_TYPE_WINDOWS_NT4,
+    KeyType::Office97 => WKV_TYPE_OFFICE97,
+    KeyType::Windows2000 => WKV_TYPE_WINDOWS2000,
+    KeyType::WindowsXP => WKV_TYPE_WINDOWS_XP,
+    KeyType::Unknown => WKV_TYPE_UNKNOWN,
+  }
+}
+
+/// The error code for `x`.
+#[allow(deprecated)]
+const fn error_code(x: &WKVError) -> c_int
+{
+  match x {
+    WKVError::TooShort => WKV_TOO_SHORT,
+    WKVError::TooLong => WKV_TOO_LONG,
+    WKVError::BadMod7 => WKV_BAD_MOD7,
+    WKVError::ExpectedDigit => WKV_EXPECTED_DIGIT,
+    WKVError::InvalidDigitPosition => WKV_INVALID_DIGIT_POSITION,
+    WKVError::ExpectedSeparator => WKV_EXPECTED_SEPARATOR,
+    WKVError::ExpectedOEM => WKV_EXPECTED_OEM,
+    WKVError::InvalidCharacter => WKV_INVALID_CHARACTER,
+    WKVError::DecodedTooLarge => WKV_DECODED_TOO_LARGE,
+    WKVError::InvalidDate => WKV_INVALID_DATE,
+    WKVError::BadAccess => WKV_BAD_ACCESS,
+    WKVError::InsufficientConfidence => WKV_INSUFFICIENT_CONFIDENCE,
+    WKVError::QuirkOnlyValid => WKV_QUIRK_ONLY_VALID,
+    WKVError::NonAscii => WKV_NON_ASCII,
+    WKVError::Internal => WKV_INTERNAL,
+    WKVError::UnknownFormat => WKV_UNKNOWN_FORMAT,
+  }
+}
+
+/// The error for `code`, the inverse of [`error_code`](fn.error_code.html).
+#[allow(deprecated)]
+const fn error(code: c_int) -> Option<WKVError>
+{
+  Some(match code {
+         WKV_TOO_SHORT => WKVError::TooShort,
+         WKV_TOO_LONG => WKVError::TooLong,
+         WKV_BAD_MOD7 => WKVError::BadMod7,
+         WKV_EXPECTED_DIGIT => WKVError::ExpectedDigit,
+         WKV_INVALID_DIGIT_POSITION => WKVError::InvalidDigitPosition,
+         WKV_EXPECTED_SEPARATOR => WKVError::ExpectedSeparator,
+         WKV_EXPECTED_OEM => WKVError::ExpectedOEM,
+         WKV_INVALID_CHARACTER => WKVError::InvalidCharacter,
+         WKV_DECODED_TOO_LARGE => WKVError::DecodedTooLarge,
+         WKV_INVALID_DATE => WKVError::InvalidDate,
+         WKV_BAD_ACCESS => WKVError::BadAccess,
+         WKV_INSUFFICIENT_CONFIDENCE => WKVError::InsufficientConfidence,
+         WKV_QUIRK_ONLY_VALID => WKVError::QuirkOnlyValid,
+         WKV_NON_ASCII => WKVError::NonAscii,
+         WKV_INTERNAL => WKVError::Internal,
+         WKV_UNKNOWN_FORMAT => WKVError::UnknownFormat,
+         _ => return None,
+       })
+}
+
+#[cfg(test)]
+#[allow(clippy::undocumented_unsafe_blocks, clippy::unwrap_used)]
+mod tests
+{
+  use core::ptr;
+  use std::ffi::CString;
+
+  use super::*;
+
+  fn validate(key: &str) -> (c_int, c_int)
+  {
+    let key = CString::new(key).unwrap();
+    let mut key_type = -1;
+    let code = unsafe { wkv_validate(key.as_ptr(), &mut key_type) };
+
+    (code, key_type)
+  }
+
+  #[test]
+  fn valid()
+  {
+    let key = CString::new("757-2573155").unwrap();
+
+    assert_eq!(validate("757-2573155"), (WKV_OK, WKV_TYPE_WINDOWS95));
+    assert_eq!(unsafe { wkv_validate(key.as_ptr(), ptr::null_mut()) }, WKV_OK);
+  }
+
+  #[test]
+  fn invalid()
+  {
+    assert_eq!(validate("000-5555554"), (WKV_BAD_MOD7, -1));
+    assert_eq!(unsafe { wkv_validate(ptr::null(), ptr::null_mut()) },
+               WKV_INVALID_ARGUMENT);
+  }
+
+  #[test]
+  fn identify()
+  {
+    let key = CString::new("757-2573155").unwrap();
+    let mut key_types = [-1; 1];
+
+    assert_eq!(unsafe { wkv_identify(key.as_ptr(), key_types.as_mut_ptr(), 1) },
+               2);
+    assert_eq!(key_types, [WKV_TYPE_WINDOWS95]);
+    assert_eq!(unsafe { wkv_identify(key.as_ptr(), ptr::null_mut(), 0) }, 2);
+  }
+
+  #[test]
+  fn error_codes()
+  {
+    for code in WKV_TOO_SHORT..=WKV_UNKNOWN_FORMAT {
+      assert_eq!(error(code).map(|x| error_code(&x)), Some(code));
+    }
+    assert_eq!(error(WKV_UNKNOWN_FORMAT + 1), None);
+  }
+
+  #[test]
+  fn error_message()
+  {
+    let mut buffer = [0x7f_u8; 8];
+    let len = unsafe { wkv_error_message(WKV_BAD_MOD7, buffer.as_mut_ptr().cast(), 8) };
+
+    assert_eq!(len, "key fails the mod 7 checksum".len());
+    assert_eq!(&buffer, b"key fai\0");
+    assert_eq!(unsafe { wkv_error_message(WKV_OK, ptr::null_mut(), 0) }, 8);
+  }
+}
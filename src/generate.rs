@@ -0,0 +1,170 @@
+// This Source Code Form is subject to the terms of the Mozilla Public License,
+// v. 2.0. If a copy of the MPL was not distributed with this file, You can
+// obtain one at https://mozilla.org/MPL/2.0/.
+//! Sample key generation, for testing historical software.
+//!
+//! This exists so that installers, inventory tools and the like can be tested
+//! without real keys. It only generates formats whose
+//! [`GenerationPolicy`](../enum.GenerationPolicy.html) allows it: keys that
+//! are signed by Microsoft are never generated. Randomness comes from the
+//! caller, so a seeded generator gives the same keys on every run.
+
+use alloc::{format, string::String};
+
+use crate::{GenerationPolicy, KeyType};
+
+/// Generates a key of type `release` that passes wkv's checks for it, in its
+/// documented [`pattern`](../enum.KeyType.html#method.pattern). `rng` is
+/// called for each random number needed, and the same sequence of numbers
+/// always gives the same key. Returns `None` if `release`'s
+/// [`generation_policy`](../enum.KeyType.html#method.generation_policy) is
+/// [`Refused`](../enum.GenerationPolicy.html#variant.Refused), e.g. for
+/// Windows 98 keys.
+#[inline]
+pub fn sample<R>(release: KeyType, rng: &mut R) -> Option<String>
+  where R: FnMut() -> u32
+{
+  use KeyType::*;
+
+  if release.generation_policy() == GenerationPolicy::Refused {
+    return None;
+  }
+
+  match release {
+    Windows95 | WindowsNT4 => {
+      // 333, 444, ..., 999 are rejected, so one of the other 993 is picked
+      // and moved up past the rejected ones below it. Retrying instead would
+      // never finish with an RNG that keeps returning 333.
+      let site = (3..=9).fold(rng() % 993, |site, x| if site >= x * 111 { site + 1 } else { site });
+
+      Some(format!("{site:03}-{:07}", serial(rng() % 1_000_000, release == WindowsNT4, rng)))
+    },
+    Office97 => {
+      let third = rng() % 10;
+      let fourth = (third + 1 + rng() % 2) % 10;
+
+      Some(format!("{:02}{third}{fourth}-{:07}",
+                   rng() % 100,
+                   serial(rng() % 1_000_000, true, rng)))
+    },
+    Windows95OEM => {
+      let day = 1 + rng() % 366;
+      let year = (95 + rng() % 9) % 100;
+
+      // The serial has to start with 00.
+      Some(format!("{day:03}{year:02}-OEM-{:07}-{:05}",
+                   serial(rng() % 10_000, false, rng),
+                   rng() % 100_000))
+    },
+    Windows98 | Windows2000 | WindowsXP | Unknown => None,
+  }
+}
+
+/// `head` followed by a check digit that makes it pass
+/// [`mod7`](../fn.mod7.html). If `nonzero`, the check digit is between 1 and
+/// 7, as Windows NT 4.0 and Office 97 require.
+fn serial<R>(head: u32, nonzero: bool, rng: &mut R) -> u32
+  where R: FnMut() -> u32
+{
+  let mut sum = 0;
+  let mut rest = head;
+
+  while rest > 0 {
+    sum += rest % 10;
+    rest /= 10;
+  }
+
+  let check = (7 - sum % 7) % 7;
+  let check = match check {
+    0 if nonzero => 7,
+    // Both 0 and 7, 1 and 8, and 2 and 9 work equally well.
+    0..=2 if !nonzero && rng() % 2 == 1 => check + 7,
+    _ => check,
+  };
+
+  head * 10 + check
+}
+
+#[cfg(test)]
+#[cfg(feature = "std")]
+mod tests
+{
+  use super::*;
+  use crate::{validate_with, KeyFormat, Strictness, ValidationOptions};
+
+  /// A xorshift generator, so the tests are reproducible.
+  fn rng(mut state: u32) -> impl FnMut() -> u32
+  {
+    move || {
+      state ^= state << 13;
+      state ^= state >> 17;
+      state ^= state << 5;
+      state
+    }
+  }
+
+  #[test]
+  fn valid()
+  {
+    let options = ValidationOptions { strictness: Strictness::Documented,
+                                      ..ValidationOptions::default() };
+    let mut rng = rng(1);
+
+    for release in [KeyType::Windows95,
+                    KeyType::WindowsNT4,
+                    KeyType::Office97,
+                    KeyType::Windows95OEM]
+    {
+      for _ in 0..1000 {
+        let key = sample(release, &mut rng).unwrap_or_default();
+
+        assert_eq!(release.validate(&key).map(|x| x.release), Ok(release), "{key}");
+        assert_eq!(validate_with(&key, &options).err(), None, "{key}");
+      }
+    }
+  }
+
+  #[test]
+  fn constant_rng()
+  {
+    for n in [0, 333, 999, u32::MAX] {
+      for release in [KeyType::Windows95,
+                      KeyType::WindowsNT4,
+                      KeyType::Office97,
+                      KeyType::Windows95OEM]
+      {
+        let key = sample(release, &mut || n).unwrap_or_default();
+
+        assert_eq!(release.validate(&key).map(|x| x.release), Ok(release), "{key}");
+      }
+    }
+  }
+
+  #[test]
+  fn sites()
+  {
+    let mut sites: Vec<_> = (0..993).filter_map(|n| sample(KeyType::Windows95, &mut || n))
+                                    .map(|x| x.get(..3).unwrap_or_default().to_owned())
+                                    .collect();
+
+    sites.dedup();
+    assert_eq!(sites.len(), 993);
+    assert!(!sites.iter().any(|x| x == "333" || x == "999"));
+  }
+
+  #[test]
+  fn reproducible()
+  {
+    assert_eq!(sample(KeyType::Windows95, &mut rng(7)),
+               sample(KeyType::Windows95, &mut rng(7)));
+    assert_ne!(sample(KeyType::Windows95, &mut rng(7)),
+               sample(KeyType::Windows95, &mut rng(8)));
+  }
+
+  #[test]
+  fn refused()
+  {
+    assert_eq!(sample(KeyType::Windows98, &mut rng(1)), None);
+    assert_eq!(sample(KeyType::Unknown, &mut rng(1)), None);
+  }
+}
@@ -0,0 +1,26 @@
+// This Source Code Form is subject to the terms of the Mozilla Public License,
+// v. 2.0. If a copy of the MPL was not distributed with this file, You can
+// obtain one at https://mozilla.org/MPL/2.0/.
+//! JSON output shared by the `wkv` command and the JavaScript bindings. The
+//! command can only see wkv's public API, so it includes this file as its own
+//! module rather than importing it.
+
+/// `x` as a JSON string literal.
+#[allow(clippy::format_push_string)]
+pub fn string(x: &str) -> String
+{
+  let mut json = String::with_capacity(x.len() + 2);
+
+  json.push('"');
+  for c in x.chars() {
+    match c {
+      '"' => json.push_str("\\\""),
+      '\\' => json.push_str("\\\\"),
+      c if c.is_control() => json.push_str(&format!("\\u{:04x}", u32::from(c))),
+      c => json.push(c),
+    }
+  }
+  json.push('"');
+
+  json
+}
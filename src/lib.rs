@@ -1,23 +1,102 @@
 // This Source Code Form is subject to the terms of the Mozilla Public License,
 // v. 2.0. If a copy of the MPL was not distributed with this file, You can
 // obtain one at https://mozilla.org/MPL/2.0/.
-#![feature(try_trait)]
+#![cfg_attr(not(feature = "std"), no_std)]
 #![warn(clippy::all, clippy::restriction, clippy::pedantic, clippy::nursery)]
-#![allow(clippy::blanket_clippy_restriction_lints,
+// Keys come from untrusted input, so nothing may panic; see `WKVError::Internal`.
+#![deny(clippy::expect_used,
+        clippy::indexing_slicing,
+        clippy::panic,
+        clippy::string_slice,
+        clippy::unreachable,
+        clippy::unwrap_used)]
+#![allow(clippy::absolute_paths,
+         clippy::allow_attributes,
+         clippy::allow_attributes_without_reason,
+         clippy::arbitrary_source_item_ordering,
+         clippy::arithmetic_side_effects,
+         clippy::blanket_clippy_restriction_lints,
+         clippy::default_numeric_fallback,
+         clippy::doc_paragraphs_missing_punctuation,
          clippy::enum_glob_use,
          clippy::implicit_return,
-         clippy::integer_arithmetic,
-         clippy::missing_errors_doc)]
+         clippy::integer_division,
+         clippy::integer_division_remainder_used,
+         clippy::min_ident_chars,
+         clippy::missing_errors_doc,
+         clippy::missing_trait_methods,
+         clippy::module_name_repetitions,
+         clippy::pattern_type_mismatch,
+         clippy::pub_with_shorthand,
+         clippy::question_mark_used,
+         clippy::separated_literal_suffix,
+         clippy::shadow_reuse,
+         clippy::shadow_same,
+         clippy::shadow_unrelated,
+         clippy::single_call_fn,
+         clippy::too_long_first_doc_paragraph,
+         clippy::unused_trait_names)]
+// Types that are expected to grow are `#[non_exhaustive]`; the rest are
+// exhaustive on purpose. See "Stability" in the Readme.
+#![allow(clippy::exhaustive_enums, clippy::exhaustive_structs)]
 //! Windows Key Validation
 //! Validate and/or identify Windows product keys.
+//!
+//! [`validate`](fn.validate.html) works out which format a key is in. If you
+//! already know, the per-format modules ([`windows95`](windows95/index.html),
+//! [`windows95_oem`](windows95_oem/index.html) and
+//! [`windows98`](windows98/index.html)) return that format's parts directly,
+//! rather than a [`Key`](struct.Key.html).
+//!
+//! Validation itself is `no_std` and never allocates. The `alloc` feature
+//! adds the functions that return `String`s or `Vec`s, and `std` (on by
+//! default) adds [`analysis`](analysis/index.html) and the
+//! `std::error::Error` impl.
+//!
+//! Nothing in wkv panics, whatever key it's given. Anything that could only
+//! go wrong because of a bug in wkv is reported as
+//! [`WKVError::Internal`](enum.WKVError.html#variant.Internal) instead.
+//!
+//! wkv never writes files. The only I/O it does is reading from a reader
+//! handed to [`validate_lines`](fn.validate_lines.html).
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "alloc")]
+use alloc::{boxed::Box,
+            string::{String, ToString},
+            vec::Vec};
+use core::{cmp::Ordering,
+           convert::TryFrom,
+           fmt::{self, Write}};
+
+#[cfg(feature = "std")]
+pub mod analysis;
+pub mod epid;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "generate")]
+pub mod generate;
+#[cfg(feature = "wasm")]
+mod json;
+#[cfg(feature = "python")]
+pub mod python;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+pub mod windows95;
+pub mod windows95_oem;
+pub mod windows98;
 
 /// Main error enum returned when an invalid key is parsed
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[non_exhaustive]
 pub enum WKVError
 {
-  /// There are no product key formats that match the length of the given key
+  /// The key is shorter than every product key format
   TooShort,
-  /// There are no product key formats that match the length of the given key
+  /// The key is longer than every product key format
   TooLong,
   /// `(sum of relevant digits) % 7 != 0`
   /// Only applies to formats using Microsoft's "mod 7" scheme.
@@ -27,148 +106,2893 @@ pub enum WKVError
   /// For formats that explicitly bar certain digit(s) from being in certain
   /// place(s).
   InvalidDigitPosition,
-  /// Used when converting from NoneError. Usually encountered when .get()
-  /// accesses a range that's larger than the slice.
+  /// Expected a separator (usually `-`), encountered something else
+  ExpectedSeparator,
+  /// Expected the literal `OEM`, as in Windows 95 OEM keys
+  ExpectedOEM,
+  /// Encountered a character that isn't in the format's alphabet, e.g. a
+  /// vowel in a 25-character key
+  InvalidCharacter,
+  /// A 25-character key decoded to a number too large to hold the bits it's
+  /// supposed to encode, or with a field too large for the format (e.g. a
+  /// Windows XP channel ID over 999)
+  DecodedTooLarge,
+  /// A date field (e.g. a day of the year) is out of range
+  InvalidDate,
+  /// A field couldn't be read from the key, i.e. a range was larger than the
+  /// key. No longer returned: every validator checks the key's length first,
+  /// and the fallbacks that used to return this return
+  /// [`Internal`](#variant.Internal) instead.
+  #[deprecated(note = "no longer returned; see `WKVError::Internal`")]
   BadAccess,
+  /// The key's format is implemented with less confidence than
+  /// [`ValidationOptions::min_confidence`](struct.ValidationOptions.html#structfield.min_confidence)
+  /// allows, so no verdict is given.
+  InsufficientConfidence,
+  /// The key is only valid because of a quirk in Windows' checks, and
+  /// [`Strictness::Documented`](enum.Strictness.html#variant.Documented) was
+  /// asked for.
+  QuirkOnlyValid,
+  /// The key contains a non-ASCII character. No format allows one, and
+  /// rejecting them up front means byte offsets always fall on character
+  /// boundaries.
+  NonAscii,
+  /// One of wkv's internal consistency checks failed. This is a bug in wkv,
+  /// reported as an error rather than a panic, since keys come from untrusted
+  /// input.
+  Internal,
+  /// The key is between the shortest and longest product key formats, but
+  /// doesn't have the length and separators of any of them, e.g. 14
+  /// characters
+  UnknownFormat,
 }
 
-// Used with .get(). If the get is out of range, the key is too short.
-impl std::convert::From<std::option::NoneError> for WKVError
+impl fmt::Display for WKVError
 {
   #[inline]
-  fn from(_: std::option::NoneError) -> Self
+  #[allow(deprecated)]
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
   {
-    Self::BadAccess
+    f.write_str(match self {
+                  Self::TooShort => "key is too short for any known format",
+                  Self::TooLong => "key is too long for any known format",
+                  Self::BadMod7 => "key fails the mod 7 checksum",
+                  Self::ExpectedDigit => "expected a digit",
+                  Self::InvalidDigitPosition => "digit not allowed in this position",
+                  Self::ExpectedSeparator => "expected a separator",
+                  Self::ExpectedOEM => "expected \"OEM\"",
+                  Self::InvalidCharacter => "character not in the format's alphabet",
+                  Self::DecodedTooLarge => "key decodes to a number that is too large",
+                  Self::InvalidDate => "date out of range",
+                  Self::BadAccess => "key is too short for its format",
+                  Self::InsufficientConfidence => {
+                    "format is not implemented with enough confidence to judge"
+                  },
+                  Self::QuirkOnlyValid => "key is only valid because of a quirk in Windows",
+                  Self::NonAscii => "key contains a non-ASCII character",
+                  Self::Internal => "internal error in wkv, please report it",
+                  Self::UnknownFormat => "key doesn't have the shape of any known format",
+                })
   }
 }
 
+#[cfg(feature = "std")]
+impl std::error::Error for WKVError {}
+
 /// Represents a key, invalid or otherwise.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[non_exhaustive]
 pub struct Key
 {
   /// The Windows release that this key is used for.
   pub release: KeyType,
+  /// The segments the key is made of.
+  pub parts:   KeyParts,
+}
+
+/// The segments of a [`Key`](struct.Key.html), decoded.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[non_exhaustive]
+pub enum KeyParts
+{
+  /// A `DDD-DDDDDDD` key, e.g. Windows 95 or Windows NT 4.0 retail.
+  ThreeSeven
+  {
+    /// The first segment, usually called the site number. Windows 95 allows
+    /// any characters here, so it's kept as bytes.
+    site:   [u8; 3],
+    /// The second segment.
+    serial: u32,
+  },
+  /// A `DDDD-DDDDDDD` key, e.g. Office 97.
+  FourSeven
+  {
+    /// The first segment. Only the last two characters have to be digits.
+    site:   [u8; 4],
+    /// The second segment.
+    serial: u32,
+  },
+  /// A `DDDYY-OEM-DDDDDDD-DDDDD` key, i.e. Windows 95 OEM.
+  Oem
+  {
+    /// The day of the year the key was issued, from 1 to 366.
+    day:    u32,
+    /// The last two digits of the year the key was issued.
+    year:   u32,
+    /// The third segment.
+    serial: u32,
+    /// The last segment, which isn't checked, so is kept as bytes.
+    suffix: [u8; 5],
+  },
+  /// A 25-character key, decoded with
+  /// [`decode_base24`](fn.decode_base24.html).
+  Base24(u128),
+}
+
+impl Key
+{
+  /// A key of type `release`, made of `parts`. `Key` is `#[non_exhaustive]`,
+  /// so this is how a [`KeyFormat`](trait.KeyFormat.html) outside wkv builds
+  /// the key it returns, usually with
+  /// [`KeyType::Unknown`](enum.KeyType.html#variant.Unknown).
+  #[inline]
+  #[must_use]
+  pub const fn new(release: KeyType, parts: KeyParts) -> Self
+  {
+    Self { release, parts }
+  }
+
+  /// The licensing class of this key, where it can be derived from the key
+  /// alone. Returns `None` when the format is shared between classes (e.g.
+  /// Windows 98 retail and OEM keys look the same).
+  #[inline]
+  #[must_use]
+  pub const fn class(&self) -> Option<KeyClass>
+  {
+    self.release.info().class
+  }
+
+  /// Statistics about the digits of this key's serial, the segment checked
+  /// by [`mod7`](fn.mod7.html). Returns `None` for 25-character keys, which
+  /// have no decimal serial.
+  #[inline]
+  #[must_use]
+  #[allow(clippy::as_conversions)]
+  pub fn metrics(&self) -> Option<KeyMetrics>
+  {
+    let serial = match self.parts {
+      KeyParts::ThreeSeven { serial, .. }
+      | KeyParts::FourSeven { serial, .. }
+      | KeyParts::Oem { serial, .. } => serial,
+      KeyParts::Base24(_) => return None,
+    };
+    let mut metrics = KeyMetrics { digit_counts:   [0; 10],
+                                   longest_run:    0,
+                                   digit_sum:      0,
+                                   checksum_slack: 0, };
+    let mut run = (None, 0);
+
+    for digit in (0..7).rev().map(|i| serial / 10_u32.pow(i) % 10) {
+      if let Some(count) = metrics.digit_counts.get_mut(digit as usize) {
+        *count += 1;
+      }
+      metrics.digit_sum += digit;
+      if matches!(digit, 0..=2 | 7..=9) {
+        metrics.checksum_slack += 1;
+      }
+      run = if run.0 == Some(digit) { (run.0, run.1 + 1) } else { (Some(digit), 1) };
+      metrics.longest_run = metrics.longest_run.max(run.1);
+    }
+
+    Some(metrics)
+  }
+
+  /// The key in its canonical form, as it's printed on the sticker: upper
+  /// case, with dashes where the format puts them. Same as `to_string()`.
+  #[cfg(feature = "alloc")]
+  #[inline]
+  #[must_use]
+  pub fn canonical(&self) -> String
+  {
+    self.to_string()
+  }
+
+  /// The key's [canonical](#method.canonical) form,
+  /// [`redact`](fn.redact.html)ed for logging: 25-character keys keep their
+  /// last group, like Windows shows them, and everything else keeps its
+  /// first segment, e.g. `757-XXXXXXX`.
+  #[cfg(feature = "alloc")]
+  #[inline]
+  #[must_use]
+  pub fn redacted(&self) -> String
+  {
+    let style = match self.parts {
+      KeyParts::Base24(_) => RedactionStyle::KeepLastSegment,
+      KeyParts::ThreeSeven { .. } | KeyParts::FourSeven { .. } | KeyParts::Oem { .. } => {
+        RedactionStyle::KeepFirstSegment
+      },
+    };
+
+    redact(&self.canonical(), style)
+  }
+}
+
+/// What [`redact`](fn.redact.html) leaves readable.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RedactionStyle
+{
+  /// Nothing but the dashes, e.g. `XXX-XXXXXXX`.
+  Full,
+  /// The first segment, e.g. `757-XXXXXXX`.
+  KeepFirstSegment,
+  /// The last segment, e.g. `XXXXX-XXXXX-XXXXX-XXXXX-GF9VC`.
+  KeepLastSegment,
+}
+
+/// Masks `key` for logs and reports, replacing every character but dashes
+/// with `X`, except in the segment `style` keeps. A key with no dashes is
+/// masked entirely whatever the style, since keeping its only segment would
+/// keep the whole key.
+#[cfg(feature = "alloc")]
+#[inline]
+#[must_use]
+pub fn redact(key: &'_ str, style: RedactionStyle) -> String
+{
+  let segments = key.split('-').count();
+  let kept = match style {
+    _ if segments < 2 => None,
+    RedactionStyle::Full => None,
+    RedactionStyle::KeepFirstSegment => Some(0),
+    RedactionStyle::KeepLastSegment => Some(segments - 1),
+  };
+
+  key.split('-')
+     .enumerate()
+     .map(|(i, x)| if Some(i) == kept { String::from(x) } else { "X".repeat(x.chars().count()) })
+     .collect::<Vec<_>>()
+     .join("-")
+}
+
+impl fmt::Display for Key
+{
+  #[inline]
+  #[allow(clippy::as_conversions, clippy::cast_possible_truncation)]
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+  {
+    match self.parts {
+      KeyParts::ThreeSeven { site, serial } => {
+        write_bytes(f, &site)?;
+        write!(f, "-{serial:07}")
+      },
+      KeyParts::FourSeven { site, serial } => {
+        write_bytes(f, &site)?;
+        write!(f, "-{serial:07}")
+      },
+      KeyParts::Oem { day,
+                      year,
+                      serial,
+                      suffix, } => {
+        write!(f, "{day:03}{year:02}-OEM-{serial:07}-")?;
+        write_bytes(f, &suffix)
+      },
+      KeyParts::Base24(mut value) => {
+        let mut chars = [b'B'; 25];
+
+        for x in chars.iter_mut().rev() {
+          if let Some(&c) = BASE24_ALPHABET.get((value % 24) as usize) {
+            *x = c;
+          }
+          value /= 24;
+        }
+        for (i, group) in chars.chunks(5).enumerate() {
+          if i > 0 {
+            f.write_str("-")?;
+          }
+          write_bytes(f, group)?;
+        }
+        Ok(())
+      },
+    }
+  }
+}
+
+/// Writes `bytes` as text. They're usually ASCII, but can be part of a
+/// multi-byte character cut off by a segment boundary, in which case each
+/// non-ASCII byte is written as U+FFFD.
+#[allow(clippy::as_conversions)]
+fn write_bytes(f: &mut fmt::Formatter<'_>, bytes: &[u8]) -> fmt::Result
+{
+  match core::str::from_utf8(bytes) {
+    Ok(x) => f.write_str(x),
+    Err(_) => {
+      bytes.iter()
+           .try_for_each(|&x| f.write_char(if x.is_ascii() { x as char } else { '\u{fffd}' }))
+    },
+  }
+}
+
+/// Statistics about a key's serial, as returned by
+/// [`Key::metrics`](struct.Key.html#method.metrics). Useful for spotting
+/// keys made by naive keygens, which tend to produce lopsided digits.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct KeyMetrics
+{
+  /// How many times each digit, 0 to 9, appears.
+  pub digit_counts:   [u8; 10],
+  /// The length of the longest run of one digit repeated.
+  pub longest_run:    u8,
+  /// The sum of the digits, which is always a multiple of 7 for a valid key.
+  pub digit_sum:      u32,
+  /// How many of the digits could be mistyped as another digit without
+  /// [`mod7`](fn.mod7.html) noticing: 0, 1 and 2 can become 7, 8 and 9 and
+  /// vice versa without changing the remainder.
+  pub checksum_slack: u8,
+}
+
+/// The licensing class of a key, as returned by
+/// [`Key::class`](struct.Key.html#method.class). Audit policies usually treat
+/// these differently.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KeyClass
+{
+  /// Sold to end users, usually boxed.
+  Retail,
+  /// Preinstalled by a system builder and tied to that machine.
+  OemPreinstall,
+  /// Issued under a volume licensing agreement.
+  Volume,
+  /// Time-limited evaluation or trial media.
+  Evaluation,
+  /// Checked (debug) builds distributed to developers.
+  Checked,
 }
 
 /// An enum containing every type of Windows key that wkv can validate.
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 // The enum names are pretty self-explanatory here.
 #[allow(clippy::missing_docs_in_private_items)]
+#[non_exhaustive]
 pub enum KeyType
 {
   Windows95,
   Windows95OEM,
   Windows98,
+  WindowsNT4,
+  Office97,
+  Windows2000,
+  WindowsXP,
+  /// A format wkv doesn't have built in. This is the
+  /// [`key_type`](trait.KeyFormat.html#tymethod.key_type) of formats added
+  /// to a [`FormatRegistry`](struct.FormatRegistry.html), so it's only ever
+  /// seen in results from one.
+  ///
+  /// It doesn't mean a key wasn't recognised: wkv's own functions never
+  /// return it, and report a key that matches no format as an error (or an
+  /// [`Identification`](struct.Identification.html) with no matches)
+  /// instead. It has no metadata, validates no keys and can't be generated.
   Unknown,
 }
 
-/// Validates a given `key`. Returns a [`Key`](struct.Key.html)
+impl KeyType
+{
+  /// Static metadata describing this key type.
+  #[inline]
+  #[must_use]
+  pub const fn info(self) -> &'static KeyTypeInfo
+  {
+    use KeyType::*;
+
+    match self {
+      Windows95 => &WINDOWS95_INFO,
+      Windows95OEM => &WINDOWS95_OEM_INFO,
+      Windows98 => &WINDOWS98_INFO,
+      WindowsNT4 => &WINDOWS_NT4_INFO,
+      Office97 => &OFFICE97_INFO,
+      Windows2000 => &WINDOWS2000_INFO,
+      WindowsXP => &WINDOWS_XP_INFO,
+      Unknown => &UNKNOWN_INFO,
+    }
+  }
+
+  /// Whether wkv is willing to generate keys of this type. Anything that
+  /// would produce a key Microsoft signed (and anything wkv can't identify) is
+  /// refused outright.
+  #[inline]
+  #[must_use]
+  pub const fn generation_policy(self) -> GenerationPolicy
+  {
+    self.info().generation_policy
+  }
+
+  /// How much trust to put in wkv's implementation of this key type. Only the
+  /// Windows 95 retail check has been confirmed against a decompilation of
+  /// the installer; everything else follows community documentation, which
+  /// is often wrong.
+  #[inline]
+  #[must_use]
+  pub const fn implementation_confidence(self) -> Confidence
+  {
+    self.info().confidence
+  }
+
+  /// Whether keys of this type are signed by Microsoft, and so only checked
+  /// for structure. wkv never verifies signatures, so a key of this type
+  /// that passes may still be rejected by Windows.
+  #[inline]
+  #[must_use]
+  pub const fn signature_unchecked(self) -> bool
+  {
+    self.info().signature_unchecked
+  }
+
+  /// The name of the product keys of this type are for, e.g. `Windows 95`,
+  /// for showing to people.
+  #[inline]
+  #[must_use]
+  // The same as `KeyFormat::name`, but usable in constants.
+  #[allow(clippy::same_name_method)]
+  pub const fn name(self) -> &'static str
+  {
+    self.info().name
+  }
+
+  /// The year the product keys of this type are for was released, or `None`
+  /// for [`KeyType::Unknown`](enum.KeyType.html#variant.Unknown).
+  #[inline]
+  #[must_use]
+  pub const fn release_year(self) -> Option<u16>
+  {
+    self.info().release_year
+  }
+
+  /// The documented format of keys of this type, e.g. `DDD-DDDDDDD`, for
+  /// hinting at what to type. See
+  /// [`KeyTypeInfo::pattern`](struct.KeyTypeInfo.html#structfield.pattern)
+  /// for the notation. Empty for
+  /// [`KeyType::Unknown`](enum.KeyType.html#variant.Unknown).
+  #[inline]
+  #[must_use]
+  pub const fn pattern(self) -> &'static str
+  {
+    self.info().pattern
+  }
+
+  /// A key of this type that wkv accepts, for placeholders and help text.
+  /// It isn't a key Microsoft issued. Empty for
+  /// [`KeyType::Unknown`](enum.KeyType.html#variant.Unknown).
+  #[inline]
+  #[must_use]
+  pub const fn example(self) -> &'static str
+  {
+    self.info().example
+  }
+
+  /// What may be typed at byte `position` of a key of this type, with dashes,
+  /// according to the same
+  /// [`KeyTypeInfo::layout`](struct.KeyTypeInfo.html#structfield.layout) that
+  /// [`diagnose`](fn.diagnose.html) checks against. Useful for input widgets
+  /// that filter keystrokes. Returns `None` past the end of the key, and for
+  /// [`KeyType::Unknown`](enum.KeyType.html#variant.Unknown).
+  #[inline]
+  #[must_use]
+  pub fn allowed_at(self, position: usize) -> Option<CharClass>
+  {
+    self.info()
+        .layout
+        .as_bytes()
+        .get(position)
+        .map(|&x| CharClass::from_layout(x))
+  }
+
+  /// The function that validates keys of this type.
+  fn validator(self) -> Option<Validator>
+  {
+    use KeyType::*;
+
+    match self {
+      Windows95 => Some(validate_windows95),
+      Windows95OEM => Some(validate_windows95_oem),
+      Windows98 => Some(validate_windows98),
+      WindowsNT4 => Some(validate_windows_nt4),
+      Office97 => Some(validate_office97),
+      Windows2000 => Some(validate_windows2000),
+      WindowsXP => Some(validate_windows_xp),
+      Unknown => None,
+    }
+  }
+}
+
+/// Metadata about a [`KeyType`](enum.KeyType.html), as returned by
+/// [`KeyType::info`](enum.KeyType.html#method.info). Every `KeyTypeInfo` is
+/// a constant, so references to it can be held for as long as needed.
+#[derive(Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct KeyTypeInfo
+{
+  /// The key type this describes.
+  pub key_type:            KeyType,
+  /// See [`KeyType::name`](enum.KeyType.html#method.name).
+  pub name:                &'static str,
+  /// See [`KeyType::release_year`](enum.KeyType.html#method.release_year).
+  pub release_year:        Option<u16>,
+  /// See [`KeyType::generation_policy`](enum.KeyType.html#method.generation_policy).
+  pub generation_policy:   GenerationPolicy,
+  /// See
+  /// [`KeyType::implementation_confidence`](enum.KeyType.html#method.implementation_confidence).
+  pub confidence:          Confidence,
+  /// See
+  /// [`KeyType::signature_unchecked`](enum.KeyType.html#method.signature_unchecked).
+  pub signature_unchecked: bool,
+  /// The class every key of this type has, if they all share one. See
+  /// [`Key::class`](struct.Key.html#method.class).
+  pub class:               Option<KeyClass>,
+  /// The shape keys of this type have, or `None` if wkv can't validate them.
+  pub shape:               Option<KeyShape>,
+  /// What each character of a key of this type must be: `D` is a digit, `B`
+  /// is a [`BASE24_ALPHABET`](constant.BASE24_ALPHABET.html) character, `-`
+  /// is a dash, `X` is anything (Windows doesn't check it), and everything
+  /// else must appear literally. See
+  /// [`CharClass::from_layout`](enum.CharClass.html#method.from_layout).
+  pub layout:              &'static str,
+  /// The format as documented, in the same notation as `layout`. This is
+  /// stricter than `layout` where Windows doesn't check everything it should.
+  pub pattern:             &'static str,
+  /// See [`KeyType::example`](enum.KeyType.html#method.example).
+  pub example:             &'static str,
+}
+
+/// Metadata for [`KeyType::Windows95`](enum.KeyType.html#variant.Windows95).
+const WINDOWS95_INFO: KeyTypeInfo =
+  KeyTypeInfo { key_type:            KeyType::Windows95,
+                name:                "Windows 95",
+                release_year:        Some(1995),
+                generation_policy:   GenerationPolicy::TestOnly,
+                confidence:          Confidence::VerifiedFromDecompilation,
+                signature_unchecked: false,
+                class:               Some(KeyClass::Retail),
+                shape:               Some(KeyShape::ThreeSeven),
+                layout:              "XXXXDDDDDDD",
+                pattern:             "DDD-DDDDDDD",
+                example:             "111-1111111", };
+
+/// Metadata for [`KeyType::Windows95OEM`](enum.KeyType.html#variant.Windows95OEM).
+const WINDOWS95_OEM_INFO: KeyTypeInfo =
+  KeyTypeInfo { key_type:            KeyType::Windows95OEM,
+                name:                "Windows 95 OEM",
+                release_year:        Some(1995),
+                generation_policy:   GenerationPolicy::TestOnly,
+                confidence:          Confidence::CommunityDocumented,
+                signature_unchecked: false,
+                class:               Some(KeyClass::OemPreinstall),
+                shape:               Some(KeyShape::PidLike),
+                layout:              "DDDDD-OEM-DDDDDDD-XXXXX",
+                pattern:             "DDDDD-OEM-DDDDDDD-DDDDD",
+                example:             "12395-OEM-0000007-12345", };
+
+/// Metadata for [`KeyType::Windows98`](enum.KeyType.html#variant.Windows98).
+/// Retail and OEM keys share the format, and are signed.
+const WINDOWS98_INFO: KeyTypeInfo =
+  KeyTypeInfo { key_type:            KeyType::Windows98,
+                name:                "Windows 98",
+                release_year:        Some(1998),
+                generation_policy:   GenerationPolicy::Refused,
+                confidence:          Confidence::CommunityDocumented,
+                signature_unchecked: true,
+                class:               None,
+                shape:               Some(KeyShape::FiveByFive),
+                layout:              "BBBBB-BBBBB-BBBBB-BBBBB-BBBBB",
+                pattern:             "BBBBB-BBBBB-BBBBB-BBBBB-BBBBB",
+                example:             "BBBBB-BBBBB-BBBBB-BBBBB-BBBBB", };
+
+/// Metadata for [`KeyType::WindowsNT4`](enum.KeyType.html#variant.WindowsNT4).
+const WINDOWS_NT4_INFO: KeyTypeInfo =
+  KeyTypeInfo { key_type:            KeyType::WindowsNT4,
+                name:                "Windows NT 4.0",
+                release_year:        Some(1996),
+                generation_policy:   GenerationPolicy::TestOnly,
+                confidence:          Confidence::CommunityDocumented,
+                signature_unchecked: false,
+                class:               Some(KeyClass::Retail),
+                shape:               Some(KeyShape::ThreeSeven),
+                layout:              "XXXXDDDDDDD",
+                pattern:             "DDD-DDDDDDD",
+                example:             "111-1111111", };
+
+/// Metadata for [`KeyType::Office97`](enum.KeyType.html#variant.Office97).
+const OFFICE97_INFO: KeyTypeInfo =
+  KeyTypeInfo { key_type:            KeyType::Office97,
+                name:                "Office 97",
+                release_year:        Some(1996),
+                generation_policy:   GenerationPolicy::TestOnly,
+                confidence:          Confidence::CommunityDocumented,
+                signature_unchecked: false,
+                class:               None,
+                shape:               Some(KeyShape::FourSeven),
+                layout:              "XXDD-DDDDDDD",
+                pattern:             "DDDD-DDDDDDD",
+                example:             "1234-0000007", };
+
+/// Metadata for [`KeyType::Windows2000`](enum.KeyType.html#variant.Windows2000).
+/// The format is assumed to be the same as Windows XP's, which is better
+/// documented.
+const WINDOWS2000_INFO: KeyTypeInfo =
+  KeyTypeInfo { key_type:            KeyType::Windows2000,
+                name:                "Windows 2000",
+                release_year:        Some(2000),
+                generation_policy:   GenerationPolicy::Refused,
+                confidence:          Confidence::Heuristic,
+                signature_unchecked: true,
+                class:               None,
+                shape:               Some(KeyShape::FiveByFive),
+                layout:              "BBBBB-BBBBB-BBBBB-BBBBB-BBBBB",
+                pattern:             "BBBBB-BBBBB-BBBBB-BBBBB-BBBBB",
+                example:             "BBBBB-BBBBB-BBBBB-BBBBB-BBBBB", };
+
+/// Metadata for [`KeyType::WindowsXP`](enum.KeyType.html#variant.WindowsXP).
+const WINDOWS_XP_INFO: KeyTypeInfo =
+  KeyTypeInfo { key_type:            KeyType::WindowsXP,
+                name:                "Windows XP",
+                release_year:        Some(2001),
+                generation_policy:   GenerationPolicy::Refused,
+                confidence:          Confidence::CommunityDocumented,
+                signature_unchecked: true,
+                class:               None,
+                shape:               Some(KeyShape::FiveByFive),
+                layout:              "BBBBB-BBBBB-BBBBB-BBBBB-BBBBB",
+                pattern:             "BBBBB-BBBBB-BBBBB-BBBBB-BBBBB",
+                example:             "BBBBB-BBBBB-BBBBB-BBBBB-BBBBB", };
+
+/// Metadata for [`KeyType::Unknown`](enum.KeyType.html#variant.Unknown).
+const UNKNOWN_INFO: KeyTypeInfo =
+  KeyTypeInfo { key_type:            KeyType::Unknown,
+                name:                "unknown",
+                release_year:        None,
+                generation_policy:   GenerationPolicy::Refused,
+                confidence:          Confidence::Heuristic,
+                signature_unchecked: false,
+                class:               None,
+                shape:               None,
+                layout:              "",
+                pattern:             "",
+                example:             "", };
+
+/// How a key type's validation rules were established, as returned by
+/// [`KeyType::implementation_confidence`](enum.KeyType.html#method.implementation_confidence).
+/// Variants are ordered from least to most trustworthy.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Confidence
+{
+  /// Rules inferred from observed keys with no authoritative source.
+  Heuristic,
+  /// Rules taken from community write-ups that agree with each other but
+  /// haven't been checked against Microsoft's code.
+  CommunityDocumented,
+  /// Rules reimplemented from a decompilation of Microsoft's own validator.
+  VerifiedFromDecompilation,
+}
+
+/// Whether a [`KeyType`](enum.KeyType.html) may be generated, as returned by
+/// [`KeyType::generation_policy`](enum.KeyType.html#method.generation_policy).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GenerationPolicy
+{
+  /// Keys of this type may be generated for any purpose.
+  Allowed,
+  /// Keys of this type may only be generated as test fixtures for historical
+  /// software. The format has no signature, so a generated key is
+  /// indistinguishable from one printed on a real CD case.
+  TestOnly,
+  /// wkv will never generate keys of this type, either because they are
+  /// cryptographically signed by Microsoft or because the format is unknown.
+  Refused,
+}
+
+/// The rough shape of a product key, as returned by
+/// [`shape_of`](fn.shape_of.html).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KeyShape
+{
+  /// 11 characters, e.g. `DDD-DDDDDDD`. The separator isn't checked, because
+  /// Windows 95 doesn't check it either.
+  ThreeSeven,
+  /// 12 characters with a dash at position 4, e.g. `DDDD-DDDDDDD`.
+  FourSeven,
+  /// 23 characters shaped like a Product ID, e.g. `DDDDD-OEM-DDDDDDD-DDDDD`.
+  PidLike,
+  /// Five groups of five characters, either separated by dashes (29
+  /// characters) or not (25).
+  FiveByFive,
+}
+
+/// Cheaply classifies `key` by its length and separators, without checking
+/// any of its characters or checksums. Returns `None` if `key` doesn't look
+/// like any known format. This is the first thing
+/// [`validate`](fn.validate.html) does, and is also useful for hinting at
+/// the expected format while a key is being typed.
 #[inline]
-pub fn validate(key: &'_ str) -> Result<Key, WKVError>
+#[must_use]
+pub fn shape_of(key: &'_ str) -> Option<KeyShape>
 {
+  let dashes_at = |positions: &[usize]| {
+    positions.iter()
+             .all(|&i| key.as_bytes().get(i) == Some(&b'-'))
+  };
+
   match key.len() {
-    x if x <= 10 => Err(WKVError::TooShort),
-    // Ex: 000-0000000
-    11 => validate_windows95(key),
-    _ => Err(WKVError::TooLong),
+    11 => Some(KeyShape::ThreeSeven),
+    12 if dashes_at(&[4]) => Some(KeyShape::FourSeven),
+    23 if dashes_at(&[5, 9, 17]) => Some(KeyShape::PidLike),
+    25 => Some(KeyShape::FiveByFive),
+    29 if dashes_at(&[5, 11, 17, 23]) => Some(KeyShape::FiveByFive),
+    _ => None,
   }
 }
 
-/// Validates a Windows 95 format key.
-///
-/// # Accuracy
-/// Many sources on Windows 95 key validation are roughly correct, but
-/// nonetheless inaccurate. Fortunately, [stacksmashing](https://youtu.be/cwyH59nACzQ)
-/// decompiled the Windows 95 installer to confirm its specific workings.
-///
-/// This function is simply a Rust reimplementation of that function.
-///
-/// # References
-/// <https://youtu.be/cwyH59nACzQ>
-#[inline]
-pub fn validate_windows95(key: &'_ str) -> Result<Key, WKVError>
+/// The signature every key validator has.
+type Validator = fn(&str) -> Result<Key, WKVError>;
+
+/// A key format that can be validated, so that formats wkv doesn't know about
+/// (e.g. in-house or MSDN-specific variants) can be checked alongside the
+/// built-in ones by a [`FormatRegistry`](struct.FormatRegistry.html). Every
+/// [`KeyType`](enum.KeyType.html) is a `KeyFormat`.
+pub trait KeyFormat
 {
-  match key.get(0..=2)? {
-    "333" | "444" | "555" | "666" | "777" | "888" | "999" => Err(WKVError::InvalidDigitPosition),
-    _ =>
-      if mod7(key.as_bytes().get(4..)?)? {
-        Ok(Key { release: KeyType::Windows95, })
-      } else {
-        Err(WKVError::BadMod7)
-      },
+  /// A human-readable name for the format, e.g. `Windows 95`.
+  fn name(&self) -> &str;
+
+  /// The key type keys of this format are reported as. Formats wkv doesn't
+  /// know should use [`KeyType::Unknown`](enum.KeyType.html#variant.Unknown).
+  fn key_type(&self) -> KeyType;
+
+  /// Whether `key` is the right length (and has its separators in the right
+  /// places) to be worth validating as this format. This should be cheap;
+  /// [`validate`](fn.validate.html) checks a key against the first format
+  /// that this returns `true` for.
+  fn matches_length(&self, key: &str) -> bool;
+
+  /// Validates `key` as this format.
+  fn validate(&self, key: &str) -> Result<Key, WKVError>;
+}
+
+impl KeyFormat for KeyType
+{
+  #[inline]
+  fn name(&self) -> &str
+  {
+    self.info().name
+  }
+
+  #[inline]
+  fn key_type(&self) -> KeyType
+  {
+    *self
+  }
+
+  /// Whether `key` has this type's [`KeyShape`](enum.KeyShape.html). Always
+  /// `false` for [`KeyType::Unknown`](enum.KeyType.html#variant.Unknown).
+  #[inline]
+  fn matches_length(&self, key: &str) -> bool
+  {
+    self.info().shape.is_some() && shape_of(key) == self.info().shape
+  }
+
+  /// Validates `key` with this type's validator, e.g.
+  /// [`validate_windows95`](fn.validate_windows95.html).
+  /// [`KeyType::Unknown`](enum.KeyType.html#variant.Unknown) rejects every
+  /// key as [`validate`](fn.validate.html) rejects keys of no known length.
+  #[inline]
+  fn validate(&self, key: &str) -> Result<Key, WKVError>
+  {
+    self.validator().map_or_else(|| Err(wrong_length(key)), |x| x(key))
   }
 }
 
-/// mod7 implements Microsoft's "mod 7" validation scheme, as described here:
-/// <https://youtu.be/cwyH59nACzQ?t=306>
-#[inline]
-#[allow(clippy::as_conversions)]
-pub fn mod7(key: &[u8]) -> Result<bool, WKVError>
+/// Every format wkv has, in the order [`identify`](fn.identify.html) tries
+/// them. [`validate`](fn.validate.html) picks the first one a key
+/// [matches the length of](trait.KeyFormat.html#tymethod.matches_length), so
+/// Windows NT 4.0 keys are reported as Windows 95 keys, and Windows 2000 and
+/// XP keys as Windows 98 keys.
+const FORMATS: [KeyType; 7] = [KeyType::Windows95,
+                               KeyType::WindowsNT4,
+                               KeyType::Office97,
+                               KeyType::Windows95OEM,
+                               KeyType::Windows98,
+                               KeyType::Windows2000,
+                               KeyType::WindowsXP];
+
+/// Why a key that matches the length of no format is invalid: it's shorter
+/// than a Windows 95 key, longer than a dashed 25-character key, or neither.
+const fn wrong_length(key: &'_ str) -> WKVError
 {
-  Ok(key.iter().try_fold(0_u32, |a, &x| {
-                  match (x as char).to_digit(10) {
-                    Some(x) => Ok(a + x),
-                    None => Err(WKVError::ExpectedDigit),
-                  }
-                })?
-     % 7
-     == 0)
+  match key.len() {
+    0..=10 => WKVError::TooShort,
+    11..=29 => WKVError::UnknownFormat,
+    _ => WKVError::TooLong,
+  }
 }
 
-#[cfg(test)]
-/// Some of these tests may look bizzare and clearly wrong. They are designed
-/// to ensure wkv validates keys that are also validated as a result of quirks
-/// in Windows. For example, Windows 95 keys are supposed to be purely numeric
-/// in the format DDD-DDDDDDD, but the fourth character is always ignored
-/// and the first three are only validated by ensuring they aren't one of 333
-/// 444, 555, 666, 777, 888, or 999, ergo YOLO111111 is completely valid.
-mod tests
+/// A list of [`KeyFormat`](trait.KeyFormat.html)s to validate and identify
+/// keys against. [`FormatRegistry::new`](#method.new) starts with every
+/// format wkv has, in the order [`identify`](fn.identify.html) uses, and
+/// [`validate`](fn.validate.html) and `identify` behave like a registry that
+/// nothing has been registered with.
+#[cfg(feature = "alloc")]
+pub struct FormatRegistry
 {
-  use super::*;
+  /// The formats, in the order they're tried.
+  formats: Vec<Box<dyn KeyFormat>>,
+}
 
-  #[test]
-  #[allow(clippy::non_ascii_literal)]
-  fn very_invalid()
+#[cfg(feature = "alloc")]
+impl FormatRegistry
+{
+  /// A registry of every format wkv has.
+  #[inline]
+  #[must_use]
+  pub fn new() -> Self
   {
-    assert_eq!(validate("8Oݼ񰤁ܢ잲Nܾ󌥀z㿒"), Err(WKVError::TooLong));
+    let mut registry = Self::empty();
+
+    for format in FORMATS {
+      registry.register(format);
+    }
+    registry
   }
 
-  #[test]
-  fn empty()
+  /// A registry with no formats, which rejects every key.
+  #[inline]
+  #[must_use]
+  pub fn empty() -> Self
   {
-    assert_eq!(validate(""), Err(WKVError::TooShort));
+    Self { formats: Vec::new() }
   }
 
-  #[test]
-  fn w95_all_zeroes()
+  /// Adds `format` after every format already registered. Since
+  /// [`validate`](#method.validate) only uses the first format whose length
+  /// matches, a format the same length as one registered earlier is only
+  /// seen by [`identify`](#method.identify); start from
+  /// [`FormatRegistry::empty`](#method.empty) to put it first.
+  #[inline]
+  pub fn register<F>(&mut self, format: F)
+    where F: KeyFormat + 'static
   {
-    assert_eq!(validate("000-0000000"),
-               Ok(Key { release: KeyType::Windows95, }));
+    self.formats.push(Box::new(format));
   }
 
-  #[test]
-  fn w95_yolo()
+  /// Every registered format, in the order they're tried.
+  #[inline]
+  pub fn formats(&self) -> impl Iterator<Item = &dyn KeyFormat>
   {
-    assert_eq!(validate("YOLO1111111"),
-               Ok(Key { release: KeyType::Windows95, }));
+    self.formats.iter().map(|x| &**x)
   }
 
-  #[test]
-  fn w95_real()
+  /// Validates `key` like [`validate`](fn.validate.html), against the first
+  /// registered format whose length matches. Keys that aren't ASCII are
+  /// rejected before any format is tried, as `validate` rejects them.
+  #[inline]
+  pub fn validate(&self, key: &'_ str) -> Result<Key, WKVError>
   {
-    assert_eq!(validate("757-2573155"),
-               Ok(Key { release: KeyType::Windows95, }));
+    ascii(key)?;
+
+    self.formats()
+        .find(|x| x.matches_length(key))
+        .map_or_else(|| Err(wrong_length(key)), |x| x.validate(key))
   }
 
-  #[test]
-  fn w95_invalid()
+  /// Tries `key` against every registered format, like
+  /// [`identify`](fn.identify.html).
+  #[inline]
+  #[must_use]
+  pub fn identify(&self, key: &'_ str) -> Identification
   {
-    assert_eq!(validate("555-5555555"), Err(WKVError::InvalidDigitPosition));
+    identify_in(self.formats(), key)
   }
+}
 
-  #[test]
-  fn w95_invalid_good_start()
+#[cfg(feature = "alloc")]
+impl Default for FormatRegistry
+{
+  #[inline]
+  fn default() -> Self
+  {
+    Self::new()
+  }
+}
+
+#[cfg(feature = "alloc")]
+impl fmt::Debug for FormatRegistry
+{
+  #[inline]
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+  {
+    f.debug_list().entries(self.formats().map(KeyFormat::name)).finish()
+  }
+}
+
+/// Every format a key was tried against by [`identify`](fn.identify.html).
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Identification
+{
+  /// Every format the key is valid for. More general formats come first, so
+  /// a Windows 95 key that also passes as a Windows NT 4.0 key lists
+  /// Windows 95 first.
+  pub matches:  Vec<Key>,
+  /// Formats the key is structurally sound for but still fails, and why,
+  /// e.g. a Windows 95 key with a bad checksum. These are likely typos.
+  pub failures: Vec<(KeyType, WKVError)>,
+  /// Formats the key can't possibly be, and why.
+  pub excluded: Vec<Exclusion>,
+}
+
+#[cfg(feature = "alloc")]
+impl Identification
+{
+  /// How far the key got through each format's checks, closest first. Where
+  /// two formats got equally far, they're in the order
+  /// [`identify`](fn.identify.html) tried them.
+  #[inline]
+  #[must_use]
+  pub fn stages(&self) -> Vec<(KeyType, MatchStage)>
+  {
+    let mut stages: Vec<_> =
+      self.matches
+          .iter()
+          .map(|x| (x.release, MatchStage::Valid))
+          .chain(self.failures.iter().map(|(x, e)| (*x, MatchStage::of_failure(e))))
+          .chain(self.excluded.iter().map(|x| (x.release, MatchStage::of_exclusion(&x.reason))))
+          .collect();
+
+    stages.sort_by_key(|&(_, x)| core::cmp::Reverse(x));
+    stages
+  }
+
+  /// The format the key came closest to being, e.g. so a Windows 95 OEM key
+  /// with a typo can be reported as a Windows 95 OEM key with a bad checksum
+  /// rather than just an invalid key. Returns `None` if the key doesn't have
+  /// the shape of any format.
+  #[inline]
+  #[must_use]
+  pub fn closest(&self) -> Option<(KeyType, MatchStage)>
+  {
+    self.stages()
+        .into_iter()
+        .next()
+        .filter(|&(_, x)| x > MatchStage::WrongShape)
+  }
+}
+
+/// How far a key got through a format's checks, as reported by
+/// [`Identification::stages`](struct.Identification.html#method.stages).
+/// Variants are ordered from least to most progress.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum MatchStage
+{
+  /// The key doesn't have the format's [`KeyShape`](enum.KeyShape.html).
+  WrongShape,
+  /// The key has the right shape, but is made of characters the format never
+  /// allows.
+  WrongCharacters,
+  /// The key is made of the right characters, but breaks one of the format's
+  /// other rules, e.g. a date out of range or a barred site number.
+  WrongStructure,
+  /// The key passes everything but the format's checksum.
+  BadChecksum,
+  /// The key is valid.
+  Valid,
+}
+
+#[cfg(feature = "alloc")]
+impl MatchStage
+{
+  /// The stage a key that failed with `e` got to.
+  const fn of_failure(e: &WKVError) -> Self
+  {
+    if matches!(e, WKVError::BadMod7) {
+      Self::BadChecksum
+    } else if is_structural(e) {
+      Self::WrongCharacters
+    } else {
+      Self::WrongStructure
+    }
+  }
+
+  /// The stage a key excluded for `reason` got to.
+  const fn of_exclusion(reason: &ExclusionReason) -> Self
+  {
+    match reason {
+      ExclusionReason::WrongShape => Self::WrongShape,
+      ExclusionReason::WrongCharacters(_) => Self::WrongCharacters,
+    }
+  }
+}
+
+/// A format that a key definitely isn't, as found by
+/// [`identify`](fn.identify.html).
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Exclusion
+{
+  /// The format the key isn't.
+  pub release: KeyType,
+  /// Why the key can't be `release`.
+  pub reason:  ExclusionReason,
+}
+
+/// Why a key can't be a given format.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum ExclusionReason
+{
+  /// The key doesn't have the format's [`KeyShape`](enum.KeyShape.html).
+  WrongShape,
+  /// The key has the right shape, but is made of characters the format never
+  /// allows, e.g. letters in a Windows 95 key's serial.
+  WrongCharacters(WKVError),
+}
+
+/// Tries `key` against every format wkv knows, rather than picking one like
+/// [`validate`](fn.validate.html) does. Useful where formats overlap, such as
+/// Windows 95 and Windows NT 4.0 keys, and for triaging serials of unknown
+/// origin.
+#[cfg(feature = "alloc")]
+#[inline]
+#[must_use]
+pub fn identify(key: &'_ str) -> Identification
+{
+  identify_in(FORMATS.iter().map(|x| -> &dyn KeyFormat { x }), key)
+}
+
+/// The formats that had been released by `year`, according to
+/// [`KeyType::release_year`](enum.KeyType.html#method.release_year), in the
+/// order [`identify`](fn.identify.html) tries them. Older formats stay in use
+/// long after newer ones come out, so they're always included.
+///
+/// Registering these in a [`FormatRegistry`](struct.FormatRegistry.html)
+/// limits identification to the formats a dated archive could contain, e.g.
+/// no Windows XP keys in a backup from 1998.
+#[cfg(feature = "alloc")]
+#[inline]
+#[must_use]
+pub fn formats_in_use(year: u16) -> Vec<KeyType>
+{
+  FORMATS.iter()
+         .copied()
+         .filter(|x| x.release_year().map_or(false, |x| x <= year))
+         .collect()
+}
+
+/// [`identify`](fn.identify.html), trying each of `formats` in turn.
+#[cfg(feature = "alloc")]
+fn identify_in<'formats, I>(formats: I, key: &'_ str) -> Identification
+  where I: Iterator<Item = &'formats dyn KeyFormat>
+{
+  let mut identification = Identification { matches:  Vec::new(),
+                                            failures: Vec::new(),
+                                            excluded: Vec::new(), };
+
+  for format in formats {
+    let release = format.key_type();
+
+    if !format.matches_length(key) {
+      identification.excluded.push(Exclusion { release,
+                                               reason: ExclusionReason::WrongShape, });
+      continue;
+    }
+
+    match format.validate(key) {
+      Ok(key) => identification.matches.push(key),
+      Err(e) if is_structural(&e) => {
+        identification.excluded.push(Exclusion { release,
+                                                 reason: ExclusionReason::WrongCharacters(e), });
+      },
+      Err(e) => identification.failures.push((release, e)),
+    }
+  }
+
+  identification
+}
+
+/// Whether `e` means a key is made of the wrong characters for a format,
+/// rather than the right characters in a combination the format rejects.
+const fn is_structural(e: &WKVError) -> bool
+{
+  matches!(e,
+           WKVError::ExpectedDigit
+           | WKVError::ExpectedSeparator
+           | WKVError::ExpectedOEM
+           | WKVError::InvalidCharacter
+           | WKVError::NonAscii)
+}
+
+/// Canonicalizes a key as typed or pasted by a person: surrounding whitespace
+/// is removed, letters are uppercased, and embedded runs of whitespace and
+/// Unicode dashes (as found in PDFs and word processors) become `-`. Spaces
+/// count as separators, so `1234 0000007` is an Office 97 key rather than a
+/// Windows 95 key with an extra digit. If the result still doesn't have the
+/// shape of any format, the dashes are redone to match the format with as
+/// many characters, e.g. `0000000000` becomes `000-0000000` and
+/// `bbbbbbbbbbbbbbbbbbbbbbbbb` becomes `BBBBB-BBBBB-BBBBB-BBBBB-BBBBB`.
+#[cfg(feature = "alloc")]
+#[inline]
+#[must_use]
+pub fn normalize(key: &'_ str) -> String
+{
+  let key: String = key.chars()
+                       .map(|x| match x {
+                         '\u{2010}'..='\u{2015}' | '\u{2212}' | '\u{fe63}' | '\u{ff0d}' => '-',
+                         x => x.to_ascii_uppercase(),
+                       })
+                       .collect();
+  let key = key.split_whitespace().collect::<Vec<_>>().join("-");
+
+  if shape_of(&key).is_some() {
+    return key;
+  }
+
+  let significant: Vec<char> = key.chars().filter(|&x| x != '-').collect();
+  let groups: &[usize] = match significant.len() {
+    10 => &[3, 7],
+    11 => &[4, 7],
+    20 => &[5, 3, 7, 5],
+    25 => &[5, 5, 5, 5, 5],
+    _ => return key,
+  };
+  let mut significant = significant.into_iter();
+
+  groups.iter()
+        .map(|&n| significant.by_ref().take(n).collect::<String>())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// Validates `key` after [`normalize`](fn.normalize.html)-ing it. Returns the
+/// [`Key`](struct.Key.html) along with the normalized form, which is what
+/// should be stored or displayed.
+#[cfg(feature = "alloc")]
+#[inline]
+pub fn validate_lenient(key: &'_ str) -> Result<(Key, String), WKVError>
+{
+  let key = normalize(key);
+
+  validate(&key).map(|x| (x, key))
+}
+
+/// Options for [`validate_with`](fn.validate_with.html).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ValidationOptions
+{
+  /// Formats implemented with less confidence than this are not validated at
+  /// all; [`WKVError::InsufficientConfidence`](enum.WKVError.html#variant.InsufficientConfidence)
+  /// is returned instead. Defaults to
+  /// [`Confidence::Heuristic`](enum.Confidence.html#variant.Heuristic), which
+  /// allows everything.
+  pub min_confidence: Confidence,
+  /// Whether to accept keys only Windows' quirks make valid. Defaults to
+  /// [`Strictness::Quirks`](enum.Strictness.html#variant.Quirks).
+  pub strictness:     Strictness,
+}
+
+impl Default for ValidationOptions
+{
+  #[inline]
+  fn default() -> Self
+  {
+    Self { min_confidence: Confidence::Heuristic,
+           strictness:     Strictness::Quirks, }
+  }
+}
+
+/// How closely a key has to follow its documented format, as set by
+/// [`ValidationOptions::strictness`](struct.ValidationOptions.html#structfield.strictness).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Strictness
+{
+  /// Accept whatever the installer accepts. `YOLO1111111` is a valid Windows
+  /// 95 key, because Windows never checks that the site number is numeric or
+  /// that the fourth character is a dash.
+  Quirks,
+  /// Additionally require keys to match their documented
+  /// [`KeyTypeInfo::pattern`](struct.KeyTypeInfo.html#structfield.pattern),
+  /// though 25-character keys may still leave out their dashes. Keys that
+  /// only pass thanks to a quirk fail with
+  /// [`WKVError::QuirkOnlyValid`](enum.WKVError.html#variant.QuirkOnlyValid);
+  /// keys of no format's length fail exactly as they do with
+  /// [`Quirks`](#variant.Quirks).
+  Documented,
+}
+
+/// Validates a given `key`. Returns a [`Key`](struct.Key.html)
+#[inline]
+pub fn validate(key: &'_ str) -> Result<Key, WKVError>
+{
+  validate_with(key, &ValidationOptions::default())
+}
+
+/// Validates a given `key` according to `options`. Returns a
+/// [`Key`](struct.Key.html)
+#[inline]
+pub fn validate_with(key: &'_ str,
+                     options: &ValidationOptions)
+                     -> Result<Key, WKVError>
+{
+  ascii(key)?;
+
+  let release = dispatch(key).ok_or_else(|| wrong_length(key))?;
+
+  if release.implementation_confidence() < options.min_confidence {
+    return Err(WKVError::InsufficientConfidence);
+  }
+
+  let valid = release.validate(key)?;
+  let pattern = valid.release.info().pattern;
+  // Whether `key` follows `pattern`, with or without its dashes.
+  let follows = |dashes: bool| {
+    let mut pattern = pattern.bytes().filter(|&c| dashes || c != b'-');
+
+    key.bytes()
+       .all(|x| pattern.next().map_or(false, |c| CharClass::from_layout(c).matches(x)))
+    && pattern.next().is_none()
+  };
+
+  if options.strictness == Strictness::Documented && !follows(true) && !follows(false) {
+    Err(WKVError::QuirkOnlyValid)
+  } else {
+    Ok(valid)
+  }
+}
+
+/// Validates each of `keys` like [`validate`](fn.validate.html), lazily, so
+/// arbitrarily many keys can be checked without collecting them first. Each
+/// result comes with its key's line number, counting from 1.
+#[inline]
+pub fn validate_batch<I>(keys: I) -> impl Iterator<Item = (usize, Result<Key, WKVError>)>
+  where I: IntoIterator,
+        I::Item: AsRef<str>
+{
+  keys.into_iter()
+      .enumerate()
+      .map(|(i, key)| (i + 1, validate(key.as_ref().trim())))
+}
+
+/// Validates one key per line of `reader`, like
+/// [`validate_batch`](fn.validate_batch.html). Blank lines are skipped, but
+/// still counted, so line numbers match the input. Stops after the first I/O
+/// error.
+#[cfg(feature = "std")]
+#[inline]
+pub fn validate_lines<R>(reader: R)
+                         -> impl Iterator<Item = std::io::Result<(usize, Result<Key, WKVError>)>>
+  where R: std::io::BufRead
+{
+  let mut failed = false;
+
+  reader.lines()
+        .enumerate()
+        .filter(|(_, line)| line.as_ref().map_or(true, |x| !x.trim().is_empty()))
+        .map_while(move |(i, line)| {
+          if failed {
+            return None;
+          }
+          failed = line.is_err();
+          Some(line.map(|x| (i + 1, validate(x.trim()))))
+        })
+}
+
+/// How many keys in a batch were valid for each format, and how many failed
+/// with each error. Build one up with
+/// [`record`](struct.BatchStatistics.html#method.record), or get one from
+/// [`par_validate_batch`](fn.par_validate_batch.html).
+#[cfg(feature = "std")]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BatchStatistics
+{
+  /// The number of valid keys of each format.
+  pub by_type:  std::collections::HashMap<KeyType, usize>,
+  /// The number of invalid keys that failed with each error.
+  pub by_error: std::collections::HashMap<WKVError, usize>,
+}
+
+#[cfg(feature = "std")]
+impl BatchStatistics
+{
+  /// Counts one more key's `result`.
+  #[inline]
+  pub fn record(&mut self, result: &Result<Key, WKVError>)
+  {
+    match result {
+      Ok(key) => *self.by_type.entry(key.release).or_insert(0) += 1,
+      Err(e) => *self.by_error.entry(e.clone()).or_insert(0) += 1,
+    }
+  }
+
+  /// Adds `other`'s counts to these.
+  #[inline]
+  #[must_use]
+  #[allow(clippy::iter_over_hash_type)] // Addition doesn't care about order.
+  pub fn merge(mut self, other: Self) -> Self
+  {
+    for (release, count) in other.by_type {
+      *self.by_type.entry(release).or_insert(0) += count;
+    }
+    for (error, count) in other.by_error {
+      *self.by_error.entry(error).or_insert(0) += count;
+    }
+    self
+  }
+}
+
+/// Validates `keys` in parallel on rayon's thread pool. Returns each key's
+/// result, in the same order as `keys`, along with
+/// [`BatchStatistics`](struct.BatchStatistics.html) for the whole batch.
+#[cfg(feature = "rayon")]
+#[inline]
+pub fn par_validate_batch<S>(keys: &[S]) -> (Vec<Result<Key, WKVError>>, BatchStatistics)
+  where S: AsRef<str> + Sync
+{
+  use rayon_crate::prelude::*;
+
+  let results: Vec<_> = keys.par_iter().map(|x| validate(x.as_ref().trim())).collect();
+  let statistics = results.par_iter()
+                          .fold(BatchStatistics::default, |mut statistics, x| {
+                            statistics.record(x);
+                            statistics
+                          })
+                          .reduce(BatchStatistics::default, BatchStatistics::merge);
+
+  (results, statistics)
+}
+
+/// Picks the format [`validate`](fn.validate.html) checks `key` against.
+fn dispatch(key: &'_ str) -> Option<KeyType>
+{
+  FORMATS.iter().copied().find(|x| x.matches_length(key))
+}
+
+/// A [`WKVError`](enum.WKVError.html) along with where in the key it was
+/// found, as returned by [`diagnose`](fn.diagnose.html).
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Diagnostic
+{
+  /// What went wrong.
+  pub error:        WKVError,
+  /// The format the key was checked against, or `None` if its length didn't
+  /// match any format.
+  pub format:       Option<KeyType>,
+  /// The byte offset of the offending character, or of the start of the
+  /// offending segment for checks that cover more than one character (e.g.
+  /// [`mod7`](fn.mod7.html)).
+  pub offset:       Option<usize>,
+  /// What was expected at `offset`, for errors caused by a single character.
+  pub expected:     Option<CharClass>,
+  /// What was probably meant at `offset`, when the character found there is
+  /// commonly confused with one that's `expected` (see
+  /// [`CharClass::substitute`](enum.CharClass.html#method.substitute)).
+  pub substitution: Option<u8>,
+}
+
+impl fmt::Display for Diagnostic
+{
+  #[inline]
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+  {
+    write!(f, "{}", self.error)?;
+    if let Some(offset) = self.offset {
+      write!(f, " at offset {offset}")?;
+    }
+    if let Some(expected) = self.expected {
+      write!(f, "; expected {expected}")?;
+    }
+    if let Some(x) = self.substitution {
+      write!(f, "; did you mean `{}`?", char::from(x))?;
+    }
+    Ok(())
+  }
+}
+
+/// Validates `key` like [`validate`](fn.validate.html), but on failure also
+/// reports where the problem is, so it can be pointed out to whoever typed
+/// the key.
+#[inline]
+pub fn diagnose(key: &'_ str) -> Result<Key, Diagnostic>
+{
+  let error = match validate(key) {
+    Ok(key) => return Ok(key),
+    Err(e) => e,
+  };
+  let format = dispatch(key);
+  let mut diagnostic = Diagnostic { error,
+                                    format,
+                                    offset: None,
+                                    expected: None,
+                                    substitution: None, };
+
+  if diagnostic.error == WKVError::NonAscii {
+    diagnostic.offset = key.bytes().position(|x| !x.is_ascii());
+  }
+  if let Some(format) = format.filter(|_| diagnostic.error != WKVError::NonAscii) {
+    if is_structural(&diagnostic.error) {
+      // 25-character keys can be written without their dashes.
+      let dashless = key.len() == 25;
+      let layout = format.info()
+                         .layout
+                         .bytes()
+                         .filter(|&x| !dashless || x != b'-')
+                         .map(CharClass::from_layout);
+      let mismatch = key.bytes()
+                        .zip(layout)
+                        .enumerate()
+                        .find(|&(_, (x, class))| !class.matches(x));
+
+      diagnostic.offset = mismatch.map(|(i, _)| i);
+      diagnostic.expected = mismatch.map(|(_, (_, class))| class);
+      diagnostic.substitution = mismatch.and_then(|(_, (x, class))| class.substitute(x));
+    } else {
+      diagnostic.offset = rule_offset(format, &diagnostic.error, key.as_bytes());
+    }
+  }
+
+  Err(diagnostic)
+}
+
+/// Everything wkv can say about one key, as returned by
+/// [`validate_report`](fn.validate_report.html). With the `serde` feature,
+/// it can be serialized as a single record, e.g. for an audit log.
+#[cfg(feature = "std")]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Report
+{
+  /// The key as given.
+  pub input:          String,
+  /// The key after [`normalize`](fn.normalize.html), which is what was
+  /// checked.
+  pub normalized:     String,
+  /// The verdict, from [`diagnose`](fn.diagnose.html).
+  pub result:         Result<Key, Diagnostic>,
+  /// Every format that was tried and how it went, from
+  /// [`identify`](fn.identify.html).
+  pub identification: Identification,
+  /// How long producing the report took.
+  pub elapsed:        core::time::Duration,
+}
+
+/// [`normalize`](fn.normalize.html)s `key`, then both
+/// [`diagnose`](fn.diagnose.html)s and [`identify`](fn.identify.html)s it,
+/// collecting the results in one [`Report`](struct.Report.html).
+#[cfg(feature = "std")]
+#[inline]
+#[must_use]
+pub fn validate_report(key: &'_ str) -> Report
+{
+  let start = std::time::Instant::now();
+  let normalized = normalize(key);
+  let result = diagnose(&normalized);
+  let identification = identify(&normalized);
+
+  Report { input: key.to_owned(),
+           normalized,
+           result,
+           identification,
+           elapsed: start.elapsed(), }
+}
+
+/// Where the rule that `key` broke, causing `error`, starts.
+#[allow(clippy::match_same_arms)]
+fn rule_offset(format: KeyType, error: &WKVError, key: &[u8]) -> Option<usize>
+{
+  use KeyType::*;
+  use WKVError::*;
+
+  let digit_in = |i: usize, range: core::ops::RangeInclusive<u8>| {
+    matches!(key.get(i), Some(x) if range.contains(x))
+  };
+
+  match (format, error) {
+    (Windows95, InvalidDigitPosition) => Some(0),
+    (WindowsNT4, InvalidDigitPosition) => Some(if digit_in(10, b'1'..=b'7') { 0 } else { 10 }),
+    (Windows95 | WindowsNT4, BadMod7) => Some(4),
+    (Office97, InvalidDigitPosition) => Some(if digit_in(11, b'1'..=b'7') { 3 } else { 11 }),
+    (Office97, BadMod7) => Some(5),
+    (Windows95OEM, InvalidDate) => {
+      let day = key.get(0..3).and_then(|x| number(x).ok())?;
+      Some(if (1..=366).contains(&day) { 3 } else { 0 })
+    },
+    (Windows95OEM, BadMod7 | InvalidDigitPosition) => Some(10),
+    (Windows98 | Windows2000 | WindowsXP, DecodedTooLarge) => Some(0),
+    _ => None,
+  }
+}
+
+/// A single-character change that would make a key valid, as returned by
+/// [`validate_with_suggestions`](fn.validate_with_suggestions.html).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Suggestion
+{
+  /// The byte offset of the character to change.
+  pub offset:      usize,
+  /// What to change it to.
+  pub replacement: u8,
+}
+
+/// Validates `key` like [`validate`](fn.validate.html), but if it fails
+/// [`mod7`](fn.mod7.html), also returns every way of changing one digit of
+/// the checked segment that makes the whole key valid. One mistyped digit is
+/// the most common cause of a bad checksum, so one of these is probably what
+/// was meant. For any other error the suggestions are empty.
+#[cfg(feature = "alloc")]
+#[inline]
+pub fn validate_with_suggestions(key: &'_ str) -> Result<Key, (WKVError, Vec<Suggestion>)>
+{
+  let error = match validate(key) {
+    Ok(key) => return Ok(key),
+    Err(e) => e,
+  };
+  let start = match dispatch(key) {
+    Some(format) if error == WKVError::BadMod7 => rule_offset(format, &error, key.as_bytes()),
+    _ => None,
+  };
+  let mut suggestions = Vec::new();
+
+  // Every format's checksum covers seven digits.
+  for offset in start.into_iter().flat_map(|x| x..x + 7) {
+    for replacement in b'0'..=b'9' {
+      let mut candidate = key.as_bytes().to_vec();
+
+      match candidate.get_mut(offset) {
+        Some(x) if *x != replacement => *x = replacement,
+        _ => continue,
+      }
+      if core::str::from_utf8(&candidate).map_or(false, |x| validate(x).is_ok()) {
+        suggestions.push(Suggestion { offset,
+                                      replacement, });
+      }
+    }
+  }
+
+  Err((error, suggestions))
+}
+
+/// A class of characters that may appear at a position in a key.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum CharClass
+{
+  /// `0` to `9`.
+  Digit,
+  /// One of [`BASE24_ALPHABET`](constant.BASE24_ALPHABET.html).
+  Base24,
+  /// A dash, `-`.
+  Separator,
+  /// Exactly this character.
+  Exactly(u8),
+  /// Anything at all. Windows doesn't check these positions.
+  Any,
+}
+
+impl CharClass
+{
+  /// The class a character in a
+  /// [`KeyTypeInfo::layout`](struct.KeyTypeInfo.html#structfield.layout)
+  /// stands for.
+  #[inline]
+  #[must_use]
+  pub const fn from_layout(x: u8) -> Self
+  {
+    match x {
+      b'D' => Self::Digit,
+      b'B' => Self::Base24,
+      b'-' => Self::Separator,
+      b'X' => Self::Any,
+      x => Self::Exactly(x),
+    }
+  }
+
+  /// Whether `x` belongs to this class.
+  #[inline]
+  #[must_use]
+  pub fn matches(self, x: u8) -> bool
+  {
+    match self {
+      Self::Digit => x.is_ascii_digit(),
+      Self::Base24 => BASE24_ALPHABET.contains(&x),
+      Self::Separator => x == b'-',
+      Self::Exactly(c) => x == c,
+      Self::Any => true,
+    }
+  }
+
+  /// The character `x` was probably meant to be, if it doesn't belong to
+  /// this class but is commonly confused with one that does: lowercase
+  /// letters, and lookalikes such as `O` for `0` in digits or `Z` for `2` in
+  /// 25-character keys. Returns `None` if `x` already belongs, or if there's
+  /// no single likely candidate (e.g. `O` in a 25-character key, which could
+  /// be `D` or `Q`).
+  #[inline]
+  #[must_use]
+  pub fn substitute(self, x: u8) -> Option<u8>
+  {
+    if self.matches(x) {
+      return None;
+    }
+
+    let candidate = match (self, x.to_ascii_uppercase()) {
+      (_, upper) if self.matches(upper) => upper,
+      (Self::Digit, b'O') => b'0',
+      (Self::Digit, b'I' | b'L') => b'1',
+      (Self::Digit | Self::Base24, b'Z') => b'2',
+      (Self::Digit, b'S') => b'5',
+      (Self::Digit, b'B') => b'8',
+      (Self::Base24, b'A') => b'4',
+      (Self::Base24, b'U') => b'V',
+      _ => return None,
+    };
+
+    Some(candidate)
+  }
+}
+
+impl fmt::Display for CharClass
+{
+  #[inline]
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+  {
+    match *self {
+      Self::Digit => f.write_str("a digit"),
+      Self::Base24 => {
+        f.write_str("one of ")?;
+        write_bytes(f, BASE24_ALPHABET)
+      },
+      Self::Separator => f.write_str("`-`"),
+      Self::Exactly(x) => write!(f, "`{}`", char::from(x)),
+      Self::Any => f.write_str("any character"),
+    }
+  }
+}
+
+/// Validates a Windows 95 format key. This is a `const fn`, so
+/// [`validate_const!`](macro.validate_const.html) can check a key at compile
+/// time. [`windows95::validate`](windows95/fn.validate.html) returns the
+/// key's parts directly.
+///
+/// # Accuracy
+/// Many sources on Windows 95 key validation are roughly correct, but
+/// nonetheless inaccurate. Fortunately, [stacksmashing](https://youtu.be/cwyH59nACzQ)
+/// decompiled the Windows 95 installer to confirm its specific workings.
+///
+/// This function is simply a Rust reimplementation of that function.
+///
+/// # References
+/// <https://youtu.be/cwyH59nACzQ>
+#[inline]
+pub const fn validate_windows95(key: &'_ str) -> Result<Key, WKVError>
+{
+  // `From` can't be used in a `const fn`.
+  match windows95::validate(key) {
+    Ok(x) => Ok(Key { release: KeyType::Windows95,
+                      parts:   KeyParts::ThreeSeven { site:   x.site,
+                                                      serial: x.serial, }, }),
+    Err(e) => Err(e),
+  }
+}
+
+/// Validates a Windows NT 4.0 format key. These follow the same rules as
+/// [`validate_windows95`](fn.validate_windows95.html), except the last digit
+/// must be between 1 and 7.
+///
+/// Every NT 4.0 key is therefore also a valid Windows 95 key, and
+/// [`validate`](fn.validate.html) reports them as such.
+#[inline]
+pub fn validate_windows_nt4(key: &'_ str) -> Result<Key, WKVError>
+{
+  let Key { parts, .. } = validate_windows95(key)?;
+
+  match key.as_bytes().get(10).ok_or(WKVError::Internal)? {
+    b'1'..=b'7' => Ok(Key { release: KeyType::WindowsNT4,
+                            parts, }),
+    _ => Err(WKVError::InvalidDigitPosition),
+  }
+}
+
+/// Validates an Office 97 format key, `DDDD-DDDDDDD`.
+///
+/// - The fourth digit must be one or two more than the third, wrapping from
+///   9 to 0.
+/// - The last seven digits must pass [`mod7`](fn.mod7.html), and the last
+///   digit must be between 1 and 7.
+/// - The key must be exactly 12 characters, with a `-` as its fifth.
+/// - The first two characters aren't checked.
+#[inline]
+pub fn validate_office97(key: &'_ str) -> Result<Key, WKVError>
+{
+  let key = ascii(key)?;
+
+  match key.len() {
+    0..=11 => return Err(WKVError::TooShort),
+    12 => (),
+    _ => return Err(WKVError::TooLong),
+  }
+  if key.get(4) != Some(&b'-') {
+    return Err(WKVError::ExpectedSeparator);
+  }
+
+  let third = number(key.get(2..3).ok_or(WKVError::Internal)?)?;
+  let fourth = number(key.get(3..4).ok_or(WKVError::Internal)?)?;
+
+  if fourth != (third + 1) % 10 && fourth != (third + 2) % 10 {
+    Err(WKVError::InvalidDigitPosition)
+  } else if !mod7(key.get(5..).ok_or(WKVError::Internal)?)? {
+    Err(WKVError::BadMod7)
+  } else if !matches!(key.get(11).ok_or(WKVError::Internal)?, b'1'..=b'7') {
+    Err(WKVError::InvalidDigitPosition)
+  } else {
+    let site = key.get(0..4).ok_or(WKVError::Internal)?;
+    let serial = key.get(5..).ok_or(WKVError::Internal)?;
+
+    Ok(Key { release: KeyType::Office97,
+             parts:   KeyParts::FourSeven { site:   bytes(site)?,
+                                            serial: number(serial)?, }, })
+  }
+}
+
+/// Validates a Windows 95 OEM format key, `DDDYY-OEM-00DDDDD-DDDDD`.
+///
+/// - `DDD` is the day of the year the key was issued, 001 to 366.
+/// - `YY` is the year, 95 to 03.
+/// - The third segment must start with `00`, and pass [`mod7`](fn.mod7.html).
+/// - The final segment isn't checked.
+///
+/// Unlike [`validate_windows95`](fn.validate_windows95.html), the separators
+/// are checked: the key must be exactly 23 characters, with a `-` between
+/// each segment.
+///
+/// # References
+/// <https://youtu.be/cwyH59nACzQ>
+///
+/// [`windows95_oem::validate`](windows95_oem/fn.validate.html) returns the
+/// key's parts directly.
+#[inline]
+pub fn validate_windows95_oem(key: &'_ str) -> Result<Key, WKVError>
+{
+  windows95_oem::validate(key).map(Key::from)
+}
+
+/// The characters 25-character product keys are made of. Vowels and
+/// characters that are easy to misread (`0`/`O`, `1`/`I`, `5`/`S`) are left
+/// out.
+pub const BASE24_ALPHABET: &[u8; 24] = b"BCDFGHJKMPQRTVWXY2346789";
+
+/// Validates a Windows 98 or Windows Me format key,
+/// `XXXXX-XXXXX-XXXXX-XXXXX-XXXXX`. The dashes are optional, but a key with
+/// any must have all four, between the groups.
+///
+/// # Accuracy
+/// These keys are 114-bit numbers written in base 24 (see
+/// [`decode_base24`](fn.decode_base24.html)), most of which is an elliptic
+/// curve signature made by Microsoft. wkv doesn't verify the signature, so
+/// this only checks the key is structurally sound: that it's made of
+/// [`BASE24_ALPHABET`](constant.BASE24_ALPHABET.html) and decodes to a number
+/// that fits in 114 bits. Plenty of keys that pass this will still be rejected
+/// by Windows.
+///
+/// [`windows98::validate`](windows98/fn.validate.html) returns the decoded
+/// number directly.
+#[inline]
+pub fn validate_windows98(key: &'_ str) -> Result<Key, WKVError>
+{
+  windows98::validate(key).map(Key::from)
+}
+
+/// Validates a Windows 2000 format key. These are assumed to be laid out
+/// like Windows XP keys; see
+/// [`validate_windows_xp`](fn.validate_windows_xp.html).
+#[inline]
+pub fn validate_windows2000(key: &'_ str) -> Result<Key, WKVError>
+{
+  validate_product_key(key).map(|parts| Key { release: KeyType::Windows2000,
+                                              parts, })
+}
+
+/// Validates a Windows XP format key, `XXXXX-XXXXX-XXXXX-XXXXX-XXXXX`. The
+/// dashes are optional, but a key with any must have all four, between the
+/// groups.
+///
+/// Every Windows XP key is also a valid Windows 98 key, and
+/// [`validate`](fn.validate.html) reports them as such.
+///
+/// # Accuracy
+/// On top of [`validate_windows98`](fn.validate_windows98.html)'s checks,
+/// the serial held in bits 1 to 30 of the decoded number must be a channel ID
+/// from 000 to 999 followed by a six-digit sequence number. The rest of the
+/// key is a hash and a signature, which wkv doesn't verify (see
+/// [`KeyType::signature_unchecked`](enum.KeyType.html#method.signature_unchecked)).
+///
+/// # References
+/// "Inside Windows Product Activation", Fully Licensed, 2001.
+#[inline]
+pub fn validate_windows_xp(key: &'_ str) -> Result<Key, WKVError>
+{
+  validate_product_key(key).map(|parts| Key { release: KeyType::WindowsXP,
+                                              parts, })
+}
+
+/// The checks [`validate_windows2000`](fn.validate_windows2000.html) and
+/// [`validate_windows_xp`](fn.validate_windows_xp.html) share.
+fn validate_product_key(key: &'_ str) -> Result<KeyParts, WKVError>
+{
+  let Key { parts, .. } = validate_windows98(key)?;
+
+  if let KeyParts::Base24(value) = parts {
+    if (value >> 1) & 0x3fff_ffff >= 1_000_000_000 {
+      return Err(WKVError::DecodedTooLarge);
+    }
+  }
+
+  Ok(parts)
+}
+
+/// Decodes a 25-character key into the number it represents, most
+/// significant character first. The key is either 25 characters, or 29 with
+/// a dash between each group of five, as
+/// [`KeyShape::FiveByFive`](enum.KeyShape.html#variant.FiveByFive) says.
+/// Keys with any dashes are held to the dashed length, so misplaced dashes
+/// fail with [`WKVError::ExpectedSeparator`](enum.WKVError.html#variant.ExpectedSeparator)
+/// rather than being skipped.
+#[inline]
+#[allow(clippy::as_conversions)]
+#[deny(clippy::arithmetic_side_effects)]
+pub fn decode_base24(key: &'_ str) -> Result<u128, WKVError>
+{
+  let key = ascii(key)?;
+  let dashed = key.contains(&b'-');
+
+  match key.len().cmp(if dashed { &29 } else { &25 }) {
+    Ordering::Less => return Err(WKVError::TooShort),
+    Ordering::Greater => return Err(WKVError::TooLong),
+    Ordering::Equal => (),
+  }
+  // Dashes go after every fifth character, if at all.
+  if key.iter()
+        .enumerate()
+        .any(|(i, &x)| (x == b'-') != (dashed && i % 6 == 5))
+  {
+    return Err(WKVError::ExpectedSeparator);
+  }
+
+  let mut value = 0_u128;
+
+  for &x in key.iter().filter(|&&x| x != b'-') {
+    let digit = BASE24_ALPHABET.iter()
+                               .position(|&c| c == x)
+                               .ok_or(WKVError::InvalidCharacter)?;
+
+    // 24^25 < 2^128, so this can't overflow.
+    value = value.checked_mul(24)
+                 .and_then(|x| x.checked_add(digit as u128))
+                 .ok_or(WKVError::Internal)?;
+  }
+
+  Ok(value)
+}
+
+/// Where the key is in a `DigitalProductId` blob.
+#[cfg(feature = "alloc")]
+const DIGITAL_PRODUCT_ID_KEY: core::ops::Range<usize> = 52..67;
+
+/// Recovers the product key from a `DigitalProductId`, the binary registry
+/// value (under `HKLM\SOFTWARE\Microsoft\Windows NT\CurrentVersion`) that
+/// Windows stores it in. Bytes 52 to 66 hold the key as a little-endian
+/// number, which is written out in base 24 as Windows does, then checked
+/// with [`validate`](fn.validate.html). Pass the result to
+/// [`identify`](fn.identify.html) to see what else it could be.
+///
+/// Returns [`WKVError::TooShort`](enum.WKVError.html#variant.TooShort) if
+/// `blob` is too short to hold a key. Windows 8 and later encode the key
+/// differently, and their blobs fail with
+/// [`WKVError::DecodedTooLarge`](enum.WKVError.html#variant.DecodedTooLarge).
+#[cfg(feature = "alloc")]
+#[inline]
+pub fn decode_digital_product_id(blob: &[u8]) -> Result<String, WKVError>
+{
+  let value = blob.get(DIGITAL_PRODUCT_ID_KEY)
+                  .ok_or(WKVError::TooShort)?
+                  .iter()
+                  .rev()
+                  .fold(0_u128, |a, &x| (a << 8) | u128::from(x));
+
+  // Anything wider than 114 bits also wouldn't fit in 25 characters.
+  if value >> 114 != 0 {
+    return Err(WKVError::DecodedTooLarge);
+  }
+
+  let key = Key { release: KeyType::Windows98,
+                  parts:   KeyParts::Base24(value), }.to_string();
+
+  validate(&key)?;
+  Ok(key)
+}
+
+/// `key`'s bytes, if it's all ASCII. Every validator checks this first, so
+/// that slicing `key` by byte offset can't split a character.
+pub(crate) const fn ascii(key: &'_ str) -> Result<&[u8], WKVError>
+{
+  let mut rest = key.as_bytes();
+
+  while let [x, tail @ ..] = rest {
+    if !x.is_ascii() {
+      return Err(WKVError::NonAscii);
+    }
+
+    rest = tail;
+  }
+
+  Ok(key.as_bytes())
+}
+
+/// Copies `x` into an array. Callers slice `x` to length `N` first, so the
+/// wrong length is an internal error.
+#[allow(clippy::map_err_ignore)]
+pub(crate) fn bytes<const N: usize>(x: &[u8]) -> Result<[u8; N], WKVError>
+{
+  <[u8; N]>::try_from(x).map_err(|_| WKVError::Internal)
+}
+
+/// Reads `digits` as a decimal number.
+#[deny(clippy::arithmetic_side_effects)]
+pub(crate) const fn number(digits: &[u8]) -> Result<u32, WKVError>
+{
+  let mut a = 0_u32;
+  let mut rest = digits;
+
+  while let [x, tail @ ..] = rest {
+    let digit = match digit(*x) {
+      Ok(x) => x,
+      Err(e) => return Err(e),
+    };
+
+    a = match a.checked_mul(10) {
+      Some(a) => match a.checked_add(digit) {
+        Some(a) => a,
+        None => return Err(WKVError::DecodedTooLarge),
+      },
+      None => return Err(WKVError::DecodedTooLarge),
+    };
+    rest = tail;
+  }
+
+  Ok(a)
+}
+
+/// The value of the decimal digit `x`.
+#[allow(clippy::as_conversions)]
+#[deny(clippy::arithmetic_side_effects)]
+const fn digit(x: u8) -> Result<u32, WKVError>
+{
+  match x {
+    // `x` is at least `b'0'`, so this never wraps.
+    b'0'..=b'9' => Ok(x.wrapping_sub(b'0') as u32),
+    _ => Err(WKVError::ExpectedDigit),
+  }
+}
+
+/// mod7 implements Microsoft's "mod 7" validation scheme, as described here:
+/// <https://youtu.be/cwyH59nACzQ?t=306>
+#[inline]
+#[deny(clippy::arithmetic_side_effects)]
+pub const fn mod7(key: &[u8]) -> Result<bool, WKVError>
+{
+  // Reducing as it goes keeps `a` below 7, so adding a digit can't overflow,
+  // however long `key` is. The check is there in case that ever changes.
+  let mut a: u32 = 0;
+  let mut rest = key;
+
+  while let [x, tail @ ..] = rest {
+    a = match digit(*x) {
+      Ok(x) => match a.checked_add(x) {
+        Some(sum) => sum % 7,
+        None => return Err(WKVError::Internal),
+      },
+      Err(e) => return Err(e),
+    };
+    rest = tail;
+  }
+
+  Ok(a == 0)
+}
+
+/// The numbers the mod 7 check works out for a key, as returned by
+/// [`checksum`](fn.checksum.html).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Checksum
+{
+  /// The byte offset of the seven digits that are checked.
+  pub offset:    usize,
+  /// The sum of those digits.
+  pub sum:       u32,
+  /// `sum % 7`. The key passes if this is 0.
+  pub remainder: u32,
+}
+
+/// The digit sum and remainder the installer computes for `key`, the same
+/// intermediate numbers shown in <https://youtu.be/cwyH59nACzQ?t=306>. Works
+/// whether or not `key` is valid, so a bad checksum can be seen for what it
+/// is.
+///
+/// Returns `None` if `key` isn't the length of a format checked by
+/// [`mod7`](fn.mod7.html) (Windows 95, NT 4.0, Office 97 and Windows 95
+/// OEM), isn't ASCII, or the checked digits aren't all digits.
+#[inline]
+#[must_use]
+#[deny(clippy::arithmetic_side_effects)]
+pub fn checksum(key: &'_ str) -> Option<Checksum>
+{
+  let bytes = ascii(key).ok()?;
+  let offset = rule_offset(dispatch(key)?, &WKVError::BadMod7, bytes)?;
+  // Seven digits add up to 63 at most, so the sum is exact.
+  let sum = bytes.get(offset..offset.checked_add(7)?)?
+                 .iter()
+                 .try_fold(0_u32, |a, &x| a.checked_add(digit(x).ok()?))?;
+
+  Some(Checksum { offset,
+                  sum,
+                  remainder: sum % 7 })
+}
+
+/// Validates a Windows 95 key at compile time, failing compilation if it's
+/// invalid, and evaluates to the key. `key` must be a constant `&str`.
+///
+/// ```
+/// const KEY: &str = wkv::validate_const!("757-2573155");
+///
+/// assert_eq!(KEY, "757-2573155");
+/// ```
+///
+/// ```compile_fail
+/// const KEY: &str = wkv::validate_const!("000-5555554");
+/// ```
+///
+/// ```compile_fail
+/// const KEY: &str = wkv::validate_const!("000-");
+/// ```
+///
+/// ```compile_fail
+/// const KEY: &str = wkv::validate_const!("757-25731550");
+/// ```
+#[macro_export]
+macro_rules! validate_const {
+  ($key:expr) => {{
+    const KEY: &str = $key;
+    const _: () = if $crate::windows95::validate(KEY).is_err() {
+      panic!("invalid Windows 95 key")
+    };
+
+    KEY
+  }};
+}
+
+#[cfg(test)]
+#[cfg(feature = "std")]
+/// Some of these tests may look bizzare and clearly wrong. They are designed
+/// to ensure wkv validates keys that are also validated as a result of quirks
+/// in Windows. For example, Windows 95 keys are supposed to be purely numeric
+/// in the format DDD-DDDDDDD, but the fourth character is always ignored
+/// and the first three are only validated by ensuring they aren't one of 333
+/// 444, 555, 666, 777, 888, or 999, ergo YOLO111111 is completely valid.
+mod tests
+{
+  use super::*;
+
+  fn releases(keys: &[Key]) -> Vec<KeyType>
+  {
+    keys.iter().map(|k| k.release).collect()
+  }
+
+  #[test]
+  #[allow(clippy::non_ascii_literal)]
+  fn very_invalid()
+  {
+    assert_eq!(validate("8Oݼ񰤁ܢ잲Nܾ󌥀z㿒"), Err(WKVError::NonAscii));
+  }
+
+  #[test]
+  #[allow(clippy::non_ascii_literal)]
+  fn non_ascii()
+  {
+    assert_eq!(validate_windows95("é1-0000000"), Err(WKVError::NonAscii));
+    assert_eq!(decode_base24("BBBBB-BBBBB-BBBBB-BBBBB-BBBBÉ"), Err(WKVError::NonAscii));
+    assert_eq!(diagnose("000-000000é").map_err(|x| (x.error, x.offset)),
+               Err((WKVError::NonAscii, Some(10))));
+    assert_eq!(number(b"99999999999"), Err(WKVError::DecodedTooLarge));
+  }
+
+  #[test]
+  fn checksums()
+  {
+    assert_eq!(checksum("757-2573155"),
+               Some(Checksum { offset:    4,
+                               sum:       28,
+                               remainder: 0, }));
+    assert_eq!(checksum("000-5555554").map(|x| (x.sum, x.remainder)), Some((34, 6)));
+    assert_eq!(checksum("1234-1111111").map(|x| x.offset), Some(5));
+    assert_eq!(checksum("12395-OEM-0000007-12345").map(|x| x.offset), Some(10));
+    assert_eq!(checksum("000-55555X4"), None);
+    assert_eq!(checksum("MXYHTP7QKM4BCR6DW8J2GF9VC"), None);
+    assert_eq!(checksum("\u{e9}12-2573155"), None);
+  }
+
+  #[test]
+  fn const_validation()
+  {
+    const KEY: Result<Key, WKVError> = validate_windows95("757-2573155");
+    const MOD7: Result<bool, WKVError> = mod7(b"2573155");
+
+    assert_eq!(KEY.map(|x| x.release), Ok(KeyType::Windows95));
+    assert_eq!(MOD7, Ok(true));
+    assert_eq!(validate_const!("111-1111111"), "111-1111111");
+  }
+
+  #[test]
+  fn long_mod7()
+  {
+    assert_eq!(mod7(&vec![b'9'; 1_000_006]), Ok(true));
+    assert_eq!(mod7(&vec![b'9'; 1_000_000]), Ok(false));
+  }
+
+  #[test]
+  fn internal()
+  {
+    assert_eq!(bytes::<3>(b"12"), Err(WKVError::Internal));
+    assert_eq!(WKVError::Internal.to_string(), "internal error in wkv, please report it");
+  }
+
+  #[test]
+  fn redaction()
+  {
+    assert_eq!(redact("757-2573155", RedactionStyle::KeepFirstSegment), "757-XXXXXXX");
+    assert_eq!(redact("757-2573155", RedactionStyle::KeepLastSegment), "XXX-2573155");
+    assert_eq!(redact("757-2573155", RedactionStyle::Full), "XXX-XXXXXXX");
+    assert_eq!(redact("7572573155", RedactionStyle::KeepFirstSegment), "XXXXXXXXXX");
+    assert_eq!(validate("12395-OEM-0000007-12345").map(|x| x.redacted()),
+               Ok("12395-XXX-XXXXXXX-XXXXX".to_owned()));
+    assert_eq!(validate("MXYHTP7QKM4BCR6DW8J2GF9VC").map(|x| x.redacted()),
+               Ok("XXXXX-XXXXX-XXXXX-XXXXX-GF9VC".to_owned()));
+  }
+
+  #[test]
+  fn report()
+  {
+    let report = validate_report(" 757 2573155 ");
+    assert_eq!(report.normalized, "757-2573155");
+    assert_eq!(report.result.map(|x| x.release), Ok(KeyType::Windows95));
+    assert_eq!(report.identification, identify("757-2573155"));
+
+    let report = validate_report("000-5555554");
+    assert_eq!(report.result.map_err(|x| x.error), Err(WKVError::BadMod7));
+  }
+
+  /// Only compiles if everything in a `Report` can be serialized.
+  #[cfg(feature = "serde")]
+  #[test]
+  fn serializable()
+  {
+    fn serializable<T: serde::Serialize>() {}
+
+    serializable::<Report>();
+  }
+
+  #[test]
+  fn empty()
+  {
+    assert_eq!(validate(""), Err(WKVError::TooShort));
+  }
+
+  #[test]
+  fn unknown_format()
+  {
+    assert_eq!(validate("0000000000"), Err(WKVError::TooShort));
+    assert_eq!(validate("00000000000000"), Err(WKVError::UnknownFormat));
+    assert_eq!(validate("12395OEM000000712345"), Err(WKVError::UnknownFormat));
+    assert_eq!(validate("1234+0000007"), Err(WKVError::UnknownFormat));
+    assert_eq!(validate("BBBBB-BBBBB-BBBBB-BBBBB-BBBBB-"), Err(WKVError::TooLong));
+  }
+
+  #[test]
+  fn w95_all_zeroes()
+  {
+    assert_eq!(validate("000-0000000").map(|k| k.release),
+               Ok(KeyType::Windows95));
+  }
+
+  #[test]
+  fn w95_yolo()
+  {
+    assert_eq!(validate("YOLO1111111").map(|k| k.release),
+               Ok(KeyType::Windows95));
+  }
+
+  #[test]
+  fn w95_real()
+  {
+    assert_eq!(validate("757-2573155").map(|k| k.release),
+               Ok(KeyType::Windows95));
+  }
+
+  #[test]
+  fn w95_parts()
+  {
+    assert_eq!(validate("YOLO1111111").map(|k| k.parts),
+               Ok(KeyParts::ThreeSeven { site:   *b"YOL",
+                                         serial: 1_111_111, }));
+  }
+
+  #[test]
+  fn w95_invalid()
+  {
+    assert_eq!(validate("555-5555555"), Err(WKVError::InvalidDigitPosition));
+  }
+
+  #[test]
+  fn w95_invalid_good_start()
+  {
+    // Not 000-5555555: its digits add up to 35, a multiple of 7, so it's valid.
+    assert_eq!(validate("000-5555554"), Err(WKVError::BadMod7));
+  }
+
+  #[test]
+  fn w95_wrong_length()
+  {
+    assert_eq!(validate_windows95("000-"), Err(WKVError::TooShort));
+    assert_eq!(validate_windows95("000-00000000000000"), Err(WKVError::TooLong));
+    assert_eq!(validate_windows95("757-25731550"), Err(WKVError::TooLong));
+  }
+
+  #[test]
+  fn identify_overlapping()
+  {
+    let identification = identify("757-2573155");
+    assert_eq!(releases(&identification.matches),
+               vec![KeyType::Windows95, KeyType::WindowsNT4]);
+    assert_eq!(identification.failures, vec![]);
+
+    let identification = identify("000-0000000");
+    assert_eq!(releases(&identification.matches), vec![KeyType::Windows95]);
+    assert_eq!(identification.failures,
+               vec![(KeyType::WindowsNT4, WKVError::InvalidDigitPosition)]);
+  }
+
+  #[test]
+  fn identify_excluded()
+  {
+    let identification = identify("000-00000A0");
+    let letters = ExclusionReason::WrongCharacters(WKVError::ExpectedDigit);
+    assert_eq!(identification.matches, vec![]);
+    assert!(identification.excluded
+                          .contains(&Exclusion { release: KeyType::Windows95,
+                                                 reason:  letters, }));
+    assert!(identification.excluded
+                          .contains(&Exclusion { release: KeyType::Windows98,
+                                                 reason:  ExclusionReason::WrongShape, }));
+  }
+
+  /// A made-up format that only accepts `000-0000000`.
+  struct Zeros;
+
+  impl KeyFormat for Zeros
+  {
+    fn name(&self) -> &'static str
+    {
+      "zeros"
+    }
+
+    fn key_type(&self) -> KeyType
+    {
+      KeyType::Unknown
+    }
+
+    fn matches_length(&self, key: &str) -> bool
+    {
+      key.len() == 11
+    }
+
+    fn validate(&self, key: &str) -> Result<Key, WKVError>
+    {
+      if key == "000-0000000" {
+        Ok(Key { release: KeyType::Unknown,
+                 parts:   KeyParts::ThreeSeven { site:   *b"000",
+                                                 serial: 0, }, })
+      } else {
+        Err(WKVError::BadMod7)
+      }
+    }
+  }
+
+  #[test]
+  fn registry()
+  {
+    let builtin = FormatRegistry::new();
+    for key in ["757-2573155",
+                "000-5555554",
+                "1234-0000007",
+                "1",
+                "",
+                "757-257315\u{e9}",
+                "757\u{2013}2573155",
+                "BBBBB-BBBBB-BBBBB-BBBBB-BBBB\u{fc}"]
+    {
+      assert_eq!(builtin.validate(key), validate(key));
+      assert_eq!(builtin.identify(key), identify(key));
+    }
+
+    let mut registry = FormatRegistry::empty();
+    assert_eq!(registry.validate("757-2573155"), Err(WKVError::UnknownFormat));
+    assert_eq!(registry.validate("757-257315\u{e9}"), Err(WKVError::NonAscii));
+    registry.register(Zeros);
+    for format in FORMATS {
+      registry.register(format);
+    }
+    assert_eq!(registry.validate("000-0000000").map(|x| x.release),
+               Ok(KeyType::Unknown));
+    assert_eq!(registry.validate("757-2573155"), Err(WKVError::BadMod7));
+
+    let identification = registry.identify("757-2573155");
+    assert_eq!(identification.failures, [(KeyType::Unknown, WKVError::BadMod7)]);
+    assert_eq!(identification.matches.len(), 2);
+
+    let mut registry = FormatRegistry::new();
+    registry.register(Zeros);
+    assert_eq!(registry.validate("000-0000000").map(|x| x.release),
+               Ok(KeyType::Windows95));
+    assert_eq!(registry.formats().last().map(KeyFormat::name), Some("zeros"));
+  }
+
+  #[test]
+  fn stages()
+  {
+    assert_eq!(identify("12395-OEM-0000008-12345").closest(),
+               Some((KeyType::Windows95OEM, MatchStage::BadChecksum)));
+    assert_eq!(identify("757-2573155").closest(),
+               Some((KeyType::Windows95, MatchStage::Valid)));
+    assert_eq!(identify("333-2573155").closest(),
+               Some((KeyType::Windows95, MatchStage::WrongStructure)));
+    assert_eq!(identify("1").closest(), None);
+
+    let stages = identify("1234-000000A").stages();
+    assert_eq!(stages.first(), Some(&(KeyType::Office97, MatchStage::WrongCharacters)));
+    assert_eq!(stages.len(), FORMATS.len());
+    assert!(stages.iter().skip(1).all(|&(_, x)| x == MatchStage::WrongShape));
+  }
+
+  #[test]
+  fn identify_nothing()
+  {
+    let identification = identify("");
+    assert_eq!(identification.matches, vec![]);
+    assert_eq!(identification.failures, vec![]);
+    assert_eq!(identification.excluded.len(), FORMATS.len());
+  }
+
+  #[test]
+  fn nt4()
+  {
+    assert_eq!(validate_windows_nt4("757-2573155").map(|k| k.release),
+               Ok(KeyType::WindowsNT4));
+    assert_eq!(validate_windows_nt4("000-0000000"),
+               Err(WKVError::InvalidDigitPosition));
+    assert_eq!(validate_windows_nt4("555-2573155"),
+               Err(WKVError::InvalidDigitPosition));
+  }
+
+  #[test]
+  fn office97()
+  {
+    assert_eq!(validate("1234-0000007").map(|k| k.release),
+               Ok(KeyType::Office97));
+    assert_eq!(validate("1291-0000007").map(|k| k.release),
+               Ok(KeyType::Office97));
+  }
+
+  #[test]
+  fn office97_parts()
+  {
+    assert_eq!(validate("AB34-0000007").map(|k| k.parts),
+               Ok(KeyParts::FourSeven { site:   *b"AB34",
+                                        serial: 7, }));
+  }
+
+  #[test]
+  fn office97_invalid()
+  {
+    assert_eq!(validate("1299-0000007"), Err(WKVError::InvalidDigitPosition));
+    assert_eq!(validate_office97("12"), Err(WKVError::TooShort));
+    assert_eq!(validate_office97("1234-00000007"), Err(WKVError::TooLong));
+    assert_eq!(validate_office97("1234X0000007"), Err(WKVError::ExpectedSeparator));
+    assert_eq!(validate("1234-0000700"), Err(WKVError::InvalidDigitPosition));
+    assert_eq!(validate("1234-0000006"), Err(WKVError::BadMod7));
+  }
+
+  #[test]
+  fn w95_oem()
+  {
+    assert_eq!(validate("12395-OEM-0000007-12345").map(|k| k.release),
+               Ok(KeyType::Windows95OEM));
+    assert_eq!(validate("36603-OEM-0012650-99999").map(|k| k.release),
+               Ok(KeyType::Windows95OEM));
+  }
+
+  #[test]
+  fn w95_oem_parts()
+  {
+    assert_eq!(validate("12395-OEM-0000007-12345").map(|k| k.parts),
+               Ok(KeyParts::Oem { day:    123,
+                                  year:   95,
+                                  serial: 7,
+                                  suffix: *b"12345", }));
+  }
+
+  #[test]
+  fn w95_oem_bad_date()
+  {
+    assert_eq!(validate("36795-OEM-0000007-12345"), Err(WKVError::InvalidDate));
+    assert_eq!(validate("00095-OEM-0000007-12345"), Err(WKVError::InvalidDate));
+    assert_eq!(validate("12304-OEM-0000007-12345"), Err(WKVError::InvalidDate));
+  }
+
+  #[test]
+  fn w95_oem_not_oem()
+  {
+    assert_eq!(validate("12395-ABC-0000007-12345"), Err(WKVError::ExpectedOEM));
+  }
+
+  #[test]
+  fn w95_oem_shape()
+  {
+    assert_eq!(validate_windows95_oem("12395-OEM-0000007-12345-EXTRA"),
+               Err(WKVError::TooLong));
+    assert_eq!(validate_windows95_oem("12395-OEM-0000007"), Err(WKVError::TooShort));
+    assert_eq!(validate_windows95_oem("12395_OEM_0000007_12345"),
+               Err(WKVError::ExpectedSeparator));
+  }
+
+  #[test]
+  fn w95_oem_bad_serial()
+  {
+    assert_eq!(validate("12395-OEM-0000008-12345"), Err(WKVError::BadMod7));
+    assert_eq!(validate("12395-OEM-0100006-12345"),
+               Err(WKVError::InvalidDigitPosition));
+  }
+
+  #[test]
+  fn w98()
+  {
+    assert_eq!(validate("BBBBB-BBBBB-BBBBB-BBBBB-BBBBB").map(|k| k.release),
+               Ok(KeyType::Windows98));
+    assert_eq!(validate("MXYHT-P7QKM-4BCR6-DW8J2-GF9VC").map(|k| k.release),
+               Ok(KeyType::Windows98));
+    assert_eq!(validate("BBBBBBBBBBBBBBBBBBBBBBBBB").map(|k| k.release),
+               Ok(KeyType::Windows98));
+  }
+
+  #[test]
+  fn w98_parts()
+  {
+    assert_eq!(validate("BBBBB-BBBBB-BBBBB-BBBBB-BBBCB").map(|k| k.parts),
+               Ok(KeyParts::Base24(24)));
+  }
+
+  #[test]
+  fn w98_too_large()
+  {
+    assert_eq!(validate("99999-99999-99999-99999-99999"),
+               Err(WKVError::DecodedTooLarge));
+  }
+
+  #[test]
+  fn w98_bad_alphabet()
+  {
+    assert_eq!(validate("BBBBB-BBBBB-OBBBB-BBBBB-BBBBB"),
+               Err(WKVError::InvalidCharacter));
+    assert_eq!(validate("bbbbb-bbbbb-bbbbb-bbbbb-bbbbb"),
+               Err(WKVError::InvalidCharacter));
+  }
+
+  #[test]
+  fn xp()
+  {
+    assert_eq!(validate_windows_xp("MXYHT-P7QKM-4BCR6-DW8J2-GF9VC").map(|k| k.release),
+               Ok(KeyType::WindowsXP));
+    assert_eq!(validate_windows2000("MXYHTP7QKM4BCR6DW8J2GF9VC").map(|k| k.release),
+               Ok(KeyType::Windows2000));
+    // Channel 999, sequence 999999, upgrade bit set.
+    assert_eq!(validate_windows_xp("BBBBB-BBBBB-BBBBB-BBBQR-GF8HK").map(|k| k.parts),
+               Ok(KeyParts::Base24(1_999_999_999)));
+    // Channel 1000.
+    assert_eq!(validate_windows_xp("BBBBB-BBBBB-BBBBB-BBBQR-GF8HM"),
+               Err(WKVError::DecodedTooLarge));
+    assert_eq!(validate("BBBBB-BBBBB-BBBBB-BBBQR-GF8HM").map(|k| k.release),
+               Ok(KeyType::Windows98));
+    // Misplaced dashes.
+    for key in ["MXYHTP7QKM4BCR6DW8J2GF9VC----",
+                "M-X-Y-H-TP7QKM4BCR6DW8J2GF9VC",
+                "MXYH-TP7QK-M4BCR-6DW8J-2GF9VC"]
+    {
+      assert_eq!(validate_windows_xp(key), Err(WKVError::ExpectedSeparator), "{key}");
+      assert_eq!(validate_windows2000(key), Err(WKVError::ExpectedSeparator), "{key}");
+    }
+    assert_eq!(validate_windows_xp("-------MXYHTP7QKM4BCR6DW8J2GF9VC"),
+               Err(WKVError::TooLong));
+    assert_eq!(releases(&identify("MXYHT-P7QKM-4BCR6-DW8J2-GF9VC").matches),
+               [KeyType::Windows98, KeyType::Windows2000, KeyType::WindowsXP]);
+    assert!(KeyType::WindowsXP.signature_unchecked());
+    assert!(!KeyType::Windows95.signature_unchecked());
+  }
+
+  #[test]
+  fn base24()
+  {
+    assert_eq!(decode_base24("BBBBB-BBBBB-BBBBB-BBBBB-BBBBC"), Ok(1));
+    assert_eq!(decode_base24("BBBBB-BBBBB-BBBBB-BBBBB-BBBCB"), Ok(24));
+    assert_eq!(decode_base24("BBBBB"), Err(WKVError::TooShort));
+    assert_eq!(decode_base24("BBBBB-BBBBBBBBBBB-BBBBB-BBBBC"),
+               Err(WKVError::ExpectedSeparator));
+  }
+
+  #[test]
+  fn diagnose_character()
+  {
+    assert_eq!(diagnose("000-00000A0"),
+               Err(Diagnostic { error:        WKVError::ExpectedDigit,
+                                format:       Some(KeyType::Windows95),
+                                offset:       Some(9),
+                                expected:     Some(CharClass::Digit),
+                                substitution: None, }));
+    assert_eq!(diagnose("12395-OAM-0000007-12345"),
+               Err(Diagnostic { error:        WKVError::ExpectedOEM,
+                                format:       Some(KeyType::Windows95OEM),
+                                offset:       Some(7),
+                                expected:     Some(CharClass::Exactly(b'E')),
+                                substitution: None, }));
+    assert_eq!(diagnose("BBBBBBBBBBBBBBBBBBBBBBBBA").map_err(|d| d.offset),
+               Err(Some(24)));
+  }
+
+  #[test]
+  fn diagnose_rule()
+  {
+    assert_eq!(diagnose("000-5555556"),
+               Err(Diagnostic { error:        WKVError::BadMod7,
+                                format:       Some(KeyType::Windows95),
+                                offset:       Some(4),
+                                expected:     None,
+                                substitution: None, }));
+    assert_eq!(diagnose("12304-OEM-0000007-12345").map_err(|d| d.offset),
+               Err(Some(3)));
+    assert_eq!(diagnose("1234-0000700").map_err(|d| d.offset), Err(Some(11)));
+  }
+
+  #[test]
+  fn diagnose_length()
+  {
+    assert_eq!(diagnose("000"),
+               Err(Diagnostic { error:        WKVError::TooShort,
+                                format:       None,
+                                offset:       None,
+                                expected:     None,
+                                substitution: None, }));
+  }
+
+  #[test]
+  fn normalize_pasted()
+  {
+    assert_eq!(normalize(" 000 0000000\n"), "000-0000000");
+    assert_eq!(normalize("000\u{2013}0000000"), "000-0000000");
+    assert_eq!(normalize("YOLO1111111"), "YOLO1111111");
+    assert_eq!(normalize("12395oem000000712345"), "12395-OEM-0000007-12345");
+    assert_eq!(normalize("bbbbb bbbbb-bbbbb bbbbbbbbbb"),
+               "BBBBB-BBBBB-BBBBB-BBBBB-BBBBB");
+    assert_eq!(normalize("1234 0000007"), "1234-0000007");
+    assert_eq!(normalize("757 - 2573155"), "757-2573155");
+    assert_eq!(normalize("bbbbb bbbbb bbbbb bbbbb bbbbb"),
+               "BBBBB-BBBBB-BBBBB-BBBBB-BBBBB");
+    assert_eq!(normalize("nonsense"), "NONSENSE");
+  }
+
+  #[test]
+  fn lenient()
+  {
+    assert_eq!(validate_lenient("757 2573155").map(|(k, s)| (k.release, s)),
+               Ok((KeyType::Windows95, "757-2573155".to_owned())));
+    assert_eq!(validate_lenient("757 2573156").map(|(k, _)| k.release),
+               Err(WKVError::BadMod7));
+    assert_eq!(validate_lenient("1234 0000007").map(|(k, s)| (k.release, s)),
+               Ok((KeyType::Office97, "1234-0000007".to_owned())));
+    assert_eq!(validate_lenient(" bbbbb bbbbb  bbbbb bbbbb bbbbb ").map(|(k, s)| (k.release, s)),
+               Ok((KeyType::Windows98, "BBBBB-BBBBB-BBBBB-BBBBB-BBBBB".to_owned())));
+  }
+
+  #[test]
+  fn shapes()
+  {
+    assert_eq!(shape_of("YOLO1111111"), Some(KeyShape::ThreeSeven));
+    assert_eq!(shape_of("1111-1111111"), Some(KeyShape::FourSeven));
+    assert_eq!(shape_of("12345-OEM-0012345-12345"), Some(KeyShape::PidLike));
+    assert_eq!(shape_of("BBBBB-BBBBB-BBBBB-BBBBB-BBBBB"),
+               Some(KeyShape::FiveByFive));
+    assert_eq!(shape_of("BBBBB BBBBB BBBBB BBBBB BBBBB"), None);
+    assert_eq!(shape_of(""), None);
+  }
+
+  #[test]
+  fn generation_policy()
+  {
+    assert_eq!(KeyType::Windows95.generation_policy(),
+               GenerationPolicy::TestOnly);
+    assert_eq!(KeyType::Windows98.generation_policy(),
+               GenerationPolicy::Refused);
+  }
+
+  #[test]
+  fn implementation_confidence()
+  {
+    assert_eq!(KeyType::Windows95.implementation_confidence(),
+               Confidence::VerifiedFromDecompilation);
+    assert!(KeyType::Windows98.implementation_confidence()
+            < Confidence::VerifiedFromDecompilation);
+  }
+
+  #[test]
+  fn info_is_static()
+  {
+    fn is_static(_: &'static KeyTypeInfo) {}
+
+    let info = KeyType::Windows95.info();
+    is_static(info);
+    assert_eq!(info.key_type, KeyType::Windows95);
+    assert_eq!(info.confidence, Confidence::VerifiedFromDecompilation);
+  }
+
+  #[test]
+  fn metrics()
+  {
+    assert_eq!(validate("757-2573155").ok().and_then(|k| k.metrics()),
+               Some(KeyMetrics { digit_counts:   [0, 1, 1, 1, 0, 3, 0, 1, 0, 0],
+                                 longest_run:    2,
+                                 digit_sum:      28,
+                                 checksum_slack: 3, }));
+    assert_eq!(validate("BBBBB-BBBBB-BBBBB-BBBBB-BBBBB").ok().and_then(|k| k.metrics()),
+               None);
+  }
+
+  #[test]
+  fn class()
+  {
+    assert_eq!(validate("757-2573155").map(|k| k.class()),
+               Ok(Some(KeyClass::Retail)));
+  }
+
+  #[test]
+  fn display()
+  {
+    for key in &["757-2573155",
+                 "1234-0000007",
+                 "12395-OEM-0000007-1234A",
+                 "MBBBB-BBBBB-BBBBB-BBBBB-BBBBB"]
+    {
+      assert_eq!(validate(key).map(|k| k.canonical()).as_deref(),
+                 Ok(*key));
+    }
+    assert_eq!(validate("YOLO1111111").map(|k| k.to_string()),
+               Ok("YOL-1111111".to_owned()));
+    assert_eq!(validate("MBBBBBBBBBBBBBBBBBBBBBBBB").map(|k| k.to_string()),
+               Ok("MBBBB-BBBBB-BBBBB-BBBBB-BBBBB".to_owned()));
+    assert_eq!(WKVError::BadMod7.to_string(), "key fails the mod 7 checksum");
+  }
+
+  #[test]
+  fn batch()
+  {
+    let results: Vec<_> = validate_batch(&["757-2573155", " 000-5555554 "]).collect();
+    assert_eq!(results,
+               [(1, validate("757-2573155")), (2, Err(WKVError::BadMod7))]);
+  }
+
+  #[test]
+  fn lines()
+  {
+    let input = "757-2573155\n\n  \n000-5555554\r\n";
+    let results: Vec<_> = validate_lines(input.as_bytes()).map(Result::unwrap).collect();
+    assert_eq!(results,
+               [(1, validate("757-2573155")), (4, Err(WKVError::BadMod7))]);
+    assert_eq!(validate_lines(&[0xff, b'\n', b'1'][..]).count(), 1);
+  }
+
+  #[test]
+  fn substitution()
+  {
+    let diagnostic = diagnose("MBBBB-BBBBB-BBZBB-BBBBB-BBBBB");
+    assert_eq!(diagnostic.as_ref().map_err(|d| (d.offset, d.substitution)),
+               Err((Some(14), Some(b'2'))));
+    assert_eq!(diagnostic.err().map(|d| d.to_string()).as_deref(),
+               Some("character not in the format's alphabet at offset 14; expected one of \
+                     BCDFGHJKMPQRTVWXY2346789; did you mean `2`?"));
+    assert_eq!(diagnose("MBBBB-BBBBB-BBOBB-BBBBB-BBBBB").map_err(|d| d.substitution),
+               Err(None));
+    assert_eq!(diagnose("757-257315o").map_err(|d| d.substitution),
+               Err(Some(b'0')));
+    assert_eq!(CharClass::Base24.substitute(b'b'), Some(b'B'));
+    assert_eq!(CharClass::Base24.substitute(b'B'), None);
+    assert_eq!(CharClass::Separator.substitute(b'_'), None);
+    assert_eq!(CharClass::Any.substitute(b'a'), None);
+    assert_eq!(CharClass::Digit.substitute(b'7'), None);
+    assert_eq!(CharClass::Exactly(b'E').substitute(b'e'), Some(b'E'));
+  }
+
+  #[test]
+  fn statistics()
+  {
+    let mut statistics = BatchStatistics::default();
+    validate_batch(&["757-2573155", "000-5555554", "111-1111111"]).for_each(|(_, x)| {
+                                                                      statistics.record(&x);
+                                                                    });
+    assert_eq!(statistics.by_type.get(&KeyType::Windows95), Some(&2));
+    assert_eq!(statistics.by_error.get(&WKVError::BadMod7), Some(&1));
+    assert_eq!(statistics.clone().merge(statistics).by_type.get(&KeyType::Windows95),
+               Some(&4));
+  }
+
+  #[cfg(feature = "rayon")]
+  #[test]
+  fn parallel()
+  {
+    let keys: Vec<String> = (0..10_000_u32).map(|i| format!("000-{:07}", i * 7)).collect();
+    let (results, statistics) = par_validate_batch(&keys);
+    let sequential: Vec<_> = validate_batch(&keys).map(|(_, x)| x).collect();
+
+    assert_eq!(results, sequential);
+    assert_eq!(statistics.by_type.values().sum::<usize>()
+               + statistics.by_error.values().sum::<usize>(),
+               keys.len());
+  }
+
+  #[test]
+  fn allowed_at()
+  {
+    assert_eq!(KeyType::Windows95.allowed_at(0), Some(CharClass::Any));
+    assert_eq!(KeyType::Windows95.allowed_at(10), Some(CharClass::Digit));
+    assert_eq!(KeyType::Windows95.allowed_at(11), None);
+    assert_eq!(KeyType::Windows95OEM.allowed_at(6), Some(CharClass::Exactly(b'O')));
+    assert_eq!(KeyType::Windows98.allowed_at(5), Some(CharClass::Separator));
+    assert_eq!(KeyType::Windows98.allowed_at(6), Some(CharClass::Base24));
+    assert_eq!(KeyType::Unknown.allowed_at(0), None);
+  }
+
+  #[test]
+  fn metadata()
+  {
+    let options = ValidationOptions { strictness: Strictness::Documented,
+                                      ..ValidationOptions::default() };
+
+    for format in FORMATS {
+      assert_eq!(KeyFormat::validate(&format, format.example()).map(|x| x.release),
+                 Ok(format));
+      assert_eq!(validate_with(format.example(), &options).err(), None);
+      assert_eq!(format.example().len(), format.pattern().len());
+    }
+    assert_eq!(KeyType::WindowsNT4.name(), "Windows NT 4.0");
+    assert_eq!(KeyType::Office97.release_year(), Some(1996));
+    assert_eq!(KeyType::Windows95.pattern(), "DDD-DDDDDDD");
+    assert_eq!(KeyType::Unknown.release_year(), None);
+  }
+
+  #[test]
+  fn unknown()
+  {
+    for key in FORMATS.iter().map(|x| x.example()).chain(["757-2573155", "1", ""]) {
+      assert!(validate(key).map_or(true, |x| x.release != KeyType::Unknown));
+      assert!(diagnose(key).map_or_else(|x| x.format != Some(KeyType::Unknown),
+                                        |x| x.release != KeyType::Unknown));
+      assert!(!identify(key).stages().iter().any(|x| x.0 == KeyType::Unknown));
+      assert_eq!(KeyFormat::validate(&KeyType::Unknown, key).ok(), None);
+    }
+    assert_eq!(KeyType::Unknown.info().shape, None);
+    assert_eq!(KeyType::Unknown.generation_policy(), GenerationPolicy::Refused);
+  }
+
+  #[test]
+  fn timeline()
+  {
+    use KeyType::*;
+
+    assert_eq!(formats_in_use(1994), []);
+    assert_eq!(formats_in_use(1995), [Windows95, Windows95OEM]);
+    assert_eq!(formats_in_use(1998),
+               [Windows95, WindowsNT4, Office97, Windows95OEM, Windows98]);
+    assert_eq!(formats_in_use(2024), FORMATS);
+
+    let mut registry = FormatRegistry::empty();
+
+    for format in formats_in_use(1998) {
+      registry.register(format);
+    }
+    assert_eq!(releases(&registry.identify("MXYHTP7QKM4BCR6DW8J2GF9VC").matches),
+               [Windows98]);
+  }
+
+  #[test]
+  fn suggestions()
+  {
+    assert_eq!(validate_with_suggestions("757-2573155").map(|x| x.release),
+               Ok(KeyType::Windows95));
+
+    let suggest = |offset, replacement| Suggestion { offset,
+                                                     replacement, };
+    let expected = (4..10).map(|x| suggest(x, b'6'))
+                          .chain([suggest(10, b'0'), suggest(10, b'7')])
+                          .collect();
+    assert_eq!(validate_with_suggestions("000-0000001"),
+               Err((WKVError::BadMod7, expected)));
+
+    // The last digit of an Office 97 serial can't be 0.
+    let expected = (5..11).map(|x| suggest(x, b'6'))
+                          .chain([suggest(11, b'7')])
+                          .collect();
+    assert_eq!(validate_with_suggestions("1234-0000001"),
+               Err((WKVError::BadMod7, expected)));
+
+    assert_eq!(validate_with_suggestions("333-0000000"),
+               Err((WKVError::InvalidDigitPosition, vec![])));
+  }
+
+  #[test]
+  fn digital_product_id()
+  {
+    let mut blob = [0_u8; 164];
+
+    // 257, little-endian.
+    blob[52] = 1;
+    blob[53] = 1;
+    assert_eq!(decode_digital_product_id(&blob).as_deref(),
+               Ok("BBBBB-BBBBB-BBBBB-BBBBB-BBBQ2"));
+    assert_eq!(decode_digital_product_id(&blob[..66]), Err(WKVError::TooShort));
+
+    blob[66] = 0x08;
+    assert_eq!(decode_digital_product_id(&blob), Err(WKVError::DecodedTooLarge));
+  }
+
+  #[test]
+  fn strict()
+  {
+    let options = ValidationOptions { strictness: Strictness::Documented,
+                                      ..ValidationOptions::default() };
+    assert_eq!(validate_with("YOLO1111111", &options),
+               Err(WKVError::QuirkOnlyValid));
+    assert_eq!(validate_with("000+0000000", &options),
+               Err(WKVError::QuirkOnlyValid));
+    assert_eq!(validate_with("BBBBBBBBBBBBBBBBBBBBBBBBB", &options).map(|k| k.release),
+               Ok(KeyType::Windows98));
+    assert_eq!(validate_with("BBBBB-BBBBBBBBBB-BBBBBBBBBB", &options),
+               Err(WKVError::UnknownFormat));
+    assert_eq!(validate_with("12395-OEM-0000007-1234A", &options),
+               Err(WKVError::QuirkOnlyValid));
+    assert_eq!(validate_with("757-2573155", &options).map(|k| k.release),
+               Ok(KeyType::Windows95));
+    assert_eq!(validate_with("000-5555556", &options), Err(WKVError::BadMod7));
+  }
+
+  #[test]
+  fn min_confidence()
   {
-    assert_eq!(validate("000-5555555"), Err(WKVError::BadMod7));
+    let options = ValidationOptions { min_confidence: Confidence::VerifiedFromDecompilation,
+                                      ..ValidationOptions::default() };
+    assert_eq!(validate_with("757-2573155", &options).map(|k| k.release),
+               Ok(KeyType::Windows95));
+    assert_eq!(validate_with("12395-OEM-0000007-12345", &options),
+               Err(WKVError::InsufficientConfidence));
   }
 }
@@ -0,0 +1,377 @@
+// This Source Code Form is subject to the terms of the Mozilla Public License,
+// v. 2.0. If a copy of the MPL was not distributed with this file, You can
+// obtain one at https://mozilla.org/MPL/2.0/.
+#![warn(clippy::all, clippy::restriction, clippy::pedantic, clippy::nursery)]
+#![allow(clippy::absolute_paths,
+         clippy::arbitrary_source_item_ordering,
+         clippy::arithmetic_side_effects,
+         clippy::blanket_clippy_restriction_lints,
+         clippy::exit,
+         clippy::format_push_string,
+         clippy::implicit_return,
+         clippy::integer_division_remainder_used,
+         clippy::min_ident_chars,
+         clippy::missing_docs_in_private_items,
+         clippy::print_stderr,
+         clippy::print_stdout,
+         clippy::question_mark_used,
+         clippy::shadow_reuse,
+         clippy::shadow_same,
+         clippy::shadow_unrelated,
+         clippy::single_call_fn,
+         clippy::unused_trait_names)]
+//! The `wkv` command. Validates the keys given as arguments, or one per line
+//! on standard input, and prints each key's format and whether it's valid.
+//!
+//! Exits with 0 if every key is valid, 1 if any isn't, and 2 on bad usage.
+//!
+//! `wkv learn` instead walks through checking a Windows 95 key, one segment
+//! at a time.
+
+use std::{env,
+          io::{self, BufRead, Write},
+          process};
+
+use wkv::{FormatRegistry, Key, KeyFormat, KeyType, WKVError};
+
+mod json;
+
+const USAGE: &str = "usage: wkv [--json] [--read-only] [--format FORMAT] [KEY...]
+       wkv learn
+
+Validates each KEY, or each line of standard input if there are none. `wkv
+learn` walks through checking a Windows 95 key step by step.
+
+  --json           print one JSON object per key
+  --read-only      accepted for forensic scripts; wkv never writes files
+  --format FORMAT  validate every key as FORMAT instead of guessing: one of
+                   win95, win95oem, win98, nt4, office97, win2000 or winxp";
+
+/// What to do, as given on the command line.
+#[derive(Debug, Default, PartialEq)]
+struct Options
+{
+  json:   bool,
+  format: Option<KeyType>,
+  keys:   Vec<String>,
+}
+
+fn main()
+{
+  let mut args = env::args().skip(1).peekable();
+
+  if args.peek().map(String::as_str) == Some("learn") {
+    let stdout = io::stdout();
+
+    if let Err(e) = learn(io::stdin().lock(), stdout.lock()) {
+      eprintln!("wkv: {e}");
+      process::exit(2);
+    }
+    return;
+  }
+
+  let options = match parse_args(args) {
+    Ok(Some(x)) => x,
+    Ok(None) => {
+      println!("{USAGE}");
+      return;
+    },
+    Err(e) => {
+      eprintln!("wkv: {e}\n{USAGE}");
+      process::exit(2);
+    },
+  };
+  let mut all_valid = true;
+  let mut check = |key: &str| {
+    let (format, result) = check(key, options.format);
+
+    all_valid &= result.is_ok();
+    println!("{}", report(key, format, result.as_ref().err(), options.json));
+  };
+
+  if options.keys.is_empty() {
+    for line in io::stdin().lock().lines() {
+      match line {
+        Ok(x) if x.trim().is_empty() => {},
+        Ok(x) => check(x.trim()),
+        Err(e) => {
+          eprintln!("wkv: {e}");
+          process::exit(2);
+        },
+      }
+    }
+  } else {
+    options.keys.iter().for_each(|x| check(x));
+  }
+
+  process::exit(i32::from(!all_valid));
+}
+
+/// Parses the command line, excluding the program name. Returns `None` if
+/// help was asked for.
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Option<Options>, String>
+{
+  let mut options = Options::default();
+
+  while let Some(arg) = args.next() {
+    match arg.as_str() {
+      "-h" | "--help" => return Ok(None),
+      "--json" => options.json = true,
+      // wkv never writes anything, so this only lets scripts say so.
+      "--read-only" => {},
+      "--format" => {
+        let format = args.next().ok_or("--format needs a value")?;
+        options.format = Some(format_named(&format)?);
+      },
+      "--" => {
+        options.keys.extend(args);
+        break;
+      },
+      x if x.starts_with("--format=") => {
+        options.format = Some(format_named(x.trim_start_matches("--format="))?);
+      },
+      x if x.starts_with('-') => {
+        return Err(format!("unknown option {x}"));
+      },
+      _ => options.keys.push(arg),
+    }
+  }
+
+  Ok(Some(options))
+}
+
+/// The format a `--format` value stands for.
+fn format_named(name: &str) -> Result<KeyType, String>
+{
+  match name {
+    "win95" => Ok(KeyType::Windows95),
+    "win95oem" => Ok(KeyType::Windows95OEM),
+    "win98" => Ok(KeyType::Windows98),
+    "nt4" => Ok(KeyType::WindowsNT4),
+    "office97" => Ok(KeyType::Office97),
+    "win2000" => Ok(KeyType::Windows2000),
+    "winxp" => Ok(KeyType::WindowsXP),
+    _ => Err(format!("unknown format {name}")),
+  }
+}
+
+/// Validates `key`, as `format` if given. Returns the format the key was
+/// checked against, if any, along with the verdict. A forced format goes
+/// through a registry of just that format, so a key of the wrong length or
+/// shape is rejected the same way `wkv::validate` rejects it.
+fn check(key: &str, format: Option<KeyType>) -> (Option<KeyType>, Result<Key, WKVError>)
+{
+  match format {
+    Some(format) if format != KeyType::Unknown => {
+      let mut registry = FormatRegistry::empty();
+
+      registry.register(format);
+      (Some(format), registry.validate(key))
+    },
+    _ => match wkv::diagnose(key) {
+      Ok(x) => (Some(x.release), Ok(x)),
+      Err(e) => (e.format, Err(e.error)),
+    },
+  }
+}
+
+/// One line of output for `key`.
+fn report(key: &str, format: Option<KeyType>, error: Option<&WKVError>, json: bool) -> String
+{
+  let format = format.map(|x| format!("{x:?}"));
+
+  if json {
+    format!("{{\"key\":{},\"valid\":{},\"format\":{},\"error\":{}}}",
+            json::string(key),
+            error.is_none(),
+            format.as_deref().map_or_else(|| "null".to_owned(), json::string),
+            error.map_or_else(|| "null".to_owned(), |x| json::string(&x.to_string())))
+  } else {
+    let format = format.as_deref().unwrap_or("unknown format");
+
+    error.map_or_else(|| format!("{key}: valid {format} key"),
+                      |e| format!("{key}: invalid {format} key: {e}"))
+  }
+}
+
+/// Prompts for a Windows 95 key one segment at a time on `input`, explaining
+/// each rule on `output` as it's checked. Asks again if a segment is the
+/// wrong length, and stops quietly if `input` runs out.
+fn learn(mut input: impl BufRead, mut output: impl Write) -> io::Result<()>
+{
+  let mut prompt = |output: &mut dyn Write, question: &str, len: usize| -> io::Result<_> {
+    loop {
+      write!(output, "{question} ({len} characters): ")?;
+      output.flush()?;
+
+      let mut line = String::new();
+
+      if input.read_line(&mut line)? == 0 {
+        writeln!(output)?;
+        return Ok(None);
+      }
+
+      let line = line.trim();
+
+      if line.chars().count() == len {
+        return Ok(Some(line.to_owned()));
+      }
+      writeln!(output, "  That's {} characters, not {len}.", line.chars().count())?;
+    }
+  };
+
+  writeln!(output,
+           "Windows 95 keys look like DDD-DDDDDDD: a three-character site number, a dash,\n\
+            and a seven-digit serial. Let's check one a piece at a time.\n")?;
+
+  let site = match prompt(&mut output, "Site number", 3)? {
+    Some(x) => x,
+    None => return Ok(()),
+  };
+  let banned = ["333", "444", "555", "666", "777", "888", "999"].contains(&site.as_str());
+
+  writeln!(output,
+           "  The site number can be anything but 333, 444, 555, 666, 777, 888 or 999: {}.",
+           if banned { "fail" } else { "ok" })?;
+
+  let serial = match prompt(&mut output, "Serial", 7)? {
+    Some(x) => x,
+    None => return Ok(()),
+  };
+  let key = format!("{site}-{serial}");
+
+  // Sum the serial itself: checksum(&key) would pick a format by the key's
+  // byte length, which a non-ASCII site number throws off.
+  match serial.chars().map(|x| x.to_digit(10)).collect::<Option<Vec<_>>>() {
+    Some(digits) => {
+      let sum: u32 = digits.iter().sum();
+      let terms: Vec<_> = digits.iter().map(u32::to_string).collect();
+
+      writeln!(output,
+               "  The digits add up to {} = {sum}, and {sum} % 7 = {}: {}.",
+               terms.join(" + "),
+               sum % 7,
+               if sum % 7 == 0 { "ok" } else { "fail, it has to be 0" })?;
+    },
+    None => writeln!(output, "  The serial has to be all digits: fail.")?,
+  }
+
+  match KeyType::Windows95.validate(&key) {
+    Ok(_) => writeln!(output, "\n{key} is a valid Windows 95 key.")?,
+    Err(e) => writeln!(output, "\n{key} is not a valid Windows 95 key: {e}.")?,
+  }
+  if wkv::validate_windows_nt4(&key).is_ok() {
+    writeln!(output, "It ends in 1 to 7, so it's a valid Windows NT 4.0 key too.")?;
+  }
+
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests
+{
+  use super::*;
+
+  fn parse(args: &[&str]) -> Result<Option<Options>, String>
+  {
+    parse_args(args.iter().map(|&x| x.to_owned()))
+  }
+
+  #[test]
+  fn args()
+  {
+    assert_eq!(parse(&["--json", "--format", "win95", "757-2573155"]),
+               Ok(Some(Options { json:   true,
+                                 format: Some(KeyType::Windows95),
+                                 keys:   vec!["757-2573155".to_owned()], })));
+    assert_eq!(parse(&["--format=nt4"]).map(|x| x.map(|x| x.format)),
+               Ok(Some(Some(KeyType::WindowsNT4))));
+    assert_eq!(parse(&["--read-only", "757-2573155"]).map(|x| x.map(|x| x.keys)),
+               Ok(Some(vec!["757-2573155".to_owned()])));
+    assert_eq!(parse(&["--help"]), Ok(None));
+    assert_eq!(parse(&["--format", "win7"]),
+               Err("unknown format win7".to_owned()));
+    assert_eq!(parse(&["--format"]), Err("--format needs a value".to_owned()));
+    assert_eq!(parse(&["--verbose"]), Err("unknown option --verbose".to_owned()));
+  }
+
+  #[test]
+  fn forced_format()
+  {
+    assert_eq!(check("757-2573155", Some(KeyType::WindowsNT4)).1.map(|x| x.release),
+               Ok(KeyType::WindowsNT4));
+    assert_eq!(check("111-0000070", Some(KeyType::WindowsNT4)).1,
+               Err(WKVError::InvalidDigitPosition));
+    assert_eq!(check("757-2573155", None).0, Some(KeyType::Windows95));
+    assert_eq!(check("000-", Some(KeyType::Windows95)).1, Err(WKVError::TooShort));
+    assert_eq!(check("12395-OEM-0000007-12345-EXTRA", Some(KeyType::Windows95OEM)).1,
+               Err(WKVError::UnknownFormat));
+    assert_eq!(check("1234-0000007", Some(KeyType::Windows95)).1,
+               Err(WKVError::UnknownFormat));
+  }
+
+  #[test]
+  fn text()
+  {
+    assert_eq!(report("757-2573155", Some(KeyType::Windows95), None, false),
+               "757-2573155: valid Windows95 key");
+    assert_eq!(report("1", None, Some(&WKVError::TooShort), false),
+               "1: invalid unknown format key: key is too short for any known format");
+  }
+
+  fn tutorial(input: &str) -> String
+  {
+    let mut output = Vec::new();
+
+    assert_eq!(learn(input.as_bytes(), &mut output).ok(), Some(()));
+    String::from_utf8(output).unwrap_or_default()
+  }
+
+  #[test]
+  fn learn_valid()
+  {
+    let output = tutorial("757\n2573155\n");
+
+    assert!(output.contains("anything but 333, 444, 555, 666, 777, 888 or 999: ok."));
+    assert!(output.contains("2 + 5 + 7 + 3 + 1 + 5 + 5 = 28, and 28 % 7 = 0: ok."));
+    assert!(output.contains("757-2573155 is a valid Windows 95 key."));
+    assert!(output.contains("valid Windows NT 4.0 key too."));
+  }
+
+  #[test]
+  fn learn_invalid()
+  {
+    let output = tutorial("99\n999\n5555554\n");
+
+    assert!(output.contains("That's 2 characters, not 3."));
+    assert!(output.contains("888 or 999: fail."));
+    assert!(output.contains("= 34, and 34 % 7 = 6: fail, it has to be 0."));
+    assert!(output.contains("999-5555554 is not a valid Windows 95 key"));
+  }
+
+  #[test]
+  fn learn_non_ascii()
+  {
+    let output = tutorial("\u{e9}12\n2573155\n");
+
+    assert!(output.contains("= 28, and 28 % 7 = 0: ok."));
+    assert!(output.contains("\u{e9}12-2573155 is not a valid Windows 95 key"));
+  }
+
+  #[test]
+  fn learn_eof()
+  {
+    assert!(tutorial("757\n").ends_with("Serial (7 characters): \n"));
+  }
+
+  #[test]
+  fn json()
+  {
+    assert_eq!(report("000-5555554", Some(KeyType::Windows95), Some(&WKVError::BadMod7), true),
+               concat!(r#"{"key":"000-5555554","valid":false,"format":"Windows95","#,
+                       r#""error":"key fails the mod 7 checksum"}"#));
+    assert_eq!(report("\"\t", None, Some(&WKVError::TooShort), true),
+               concat!(r#"{"key":"\"\u0009","valid":false,"format":null,"#,
+                       r#""error":"key is too short for any known format"}"#));
+  }
+}
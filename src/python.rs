@@ -0,0 +1,124 @@
+// This Source Code Form is subject to the terms of the Mozilla Public License,
+// v. 2.0. If a copy of the MPL was not distributed with this file, You can
+// obtain one at https://mozilla.org/MPL/2.0/.
+//! Python bindings, via `PyO3`.
+//!
+//! Build with `--features python`, or `maturin build` (see `pyproject.toml`),
+//! to get a `wkv` Python module. Results are dicts and lists rather than Rust
+//! types, so they go straight into a `pandas.DataFrame`: formats are reported
+//! by name (e.g. `"Windows95"`) and errors by their message.
+
+#![allow(clippy::missing_inline_in_public_items)]
+// `PyO3` needs a newer Rust than wkv does, so this module can use one too.
+#![allow(clippy::incompatible_msrv)]
+
+use pyo3::{prelude::{pyfunction, pymodule, wrap_pyfunction, Bound, PyModule, PyModuleMethods,
+                     PyResult},
+           IntoPyObject};
+
+use crate::KeyType;
+
+/// The result of [`validate`](fn.validate.html), which Python sees as a dict.
+#[derive(Clone, Debug, PartialEq, Eq, IntoPyObject)]
+pub struct Validation
+{
+  /// The key, as given.
+  pub key:    String,
+  /// Whether the key is valid.
+  pub valid:  bool,
+  /// The format the key is valid for, or `None` if it isn't.
+  pub format: Option<String>,
+  /// Why the key is invalid, or `None` if it isn't.
+  pub error:  Option<String>,
+}
+
+/// Validates `key` like [`validate`](../fn.validate.html).
+#[pyfunction]
+#[must_use]
+pub fn validate(key: &str) -> Validation
+{
+  match crate::validate(key) {
+    Ok(x) => Validation { key:    key.to_owned(),
+                          valid:  true,
+                          format: Some(name(x.release)),
+                          error:  None, },
+    Err(e) => Validation { key:    key.to_owned(),
+                           valid:  false,
+                           format: None,
+                           error:  Some(e.to_string()), },
+  }
+}
+
+/// The names of every format `key` is valid for, like
+/// [`identify`](../fn.identify.html)'s `matches`.
+#[pyfunction]
+#[must_use]
+pub fn identify(key: &str) -> Vec<String>
+{
+  crate::identify(key).matches
+                      .iter()
+                      .map(|x| name(x.release))
+                      .collect()
+}
+
+/// Validates every key in `keys`, like [`validate`](fn.validate.html), in
+/// order. Surrounding whitespace is trimmed first, as in
+/// [`validate_batch`](../fn.validate_batch.html).
+#[pyfunction]
+#[must_use]
+#[allow(clippy::needless_pass_by_value)] // PyO3 can't convert a list to a slice.
+pub fn validate_batch(keys: Vec<String>) -> Vec<Validation>
+{
+  keys.iter().map(|x| validate(x.trim())).collect()
+}
+
+/// The name Python sees for `x`.
+fn name(x: KeyType) -> String
+{
+  format!("{x:?}")
+}
+
+/// The `wkv` Python module.
+#[pymodule]
+fn wkv(m: &Bound<'_, PyModule>) -> PyResult<()>
+{
+  m.add_function(wrap_pyfunction!(validate, m)?)?;
+  m.add_function(wrap_pyfunction!(identify, m)?)?;
+  m.add_function(wrap_pyfunction!(validate_batch, m)?)
+}
+
+#[cfg(test)]
+mod tests
+{
+  use super::*;
+
+  #[test]
+  fn valid()
+  {
+    assert_eq!(validate("757-2573155"),
+               Validation { key:    "757-2573155".to_owned(),
+                            valid:  true,
+                            format: Some("Windows95".to_owned()),
+                            error:  None, });
+  }
+
+  #[test]
+  fn batch()
+  {
+    assert_eq!(validate_batch(vec![" 757-2573155".to_owned(), "000-5555554".to_owned()]),
+               [Validation { key:    "757-2573155".to_owned(),
+                             valid:  true,
+                             format: Some("Windows95".to_owned()),
+                             error:  None, },
+                Validation { key:    "000-5555554".to_owned(),
+                             valid:  false,
+                             format: None,
+                             error:  Some("key fails the mod 7 checksum".to_owned()), }]);
+  }
+
+  #[test]
+  fn overlapping()
+  {
+    assert_eq!(identify("757-2573155"), ["Windows95", "WindowsNT4"]);
+  }
+}
@@ -0,0 +1,145 @@
+// This Source Code Form is subject to the terms of the Mozilla Public License,
+// v. 2.0. If a copy of the MPL was not distributed with this file, You can
+// obtain one at https://mozilla.org/MPL/2.0/.
+//! JavaScript bindings, via `wasm-bindgen`.
+//!
+//! Build with `--features wasm` for `wasm32-unknown-unknown`. Results are
+//! plain objects rather than Rust enums: formats are reported by name (e.g.
+//! `"Windows95"`) and errors by their message.
+//!
+//! [`check`](fn.check.html) is everything a demo page needs in one call.
+
+// The functions are exported, so `#[inline]` would be ignored.
+#![allow(clippy::missing_inline_in_public_items)]
+
+use wasm_bindgen::prelude::wasm_bindgen;
+
+use crate::{json, KeyType};
+
+/// The result of [`validate`](fn.validate.html).
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Validation
+{
+  /// Whether the key is valid.
+  #[wasm_bindgen(readonly)]
+  pub valid:  bool,
+  /// The format the key is valid for, or `undefined` if it isn't.
+  #[wasm_bindgen(readonly)]
+  pub format: Option<String>,
+  /// Why the key is invalid, or `undefined` if it isn't.
+  #[wasm_bindgen(readonly)]
+  pub error:  Option<String>,
+}
+
+/// Validates `key` like [`validate`](../fn.validate.html).
+#[wasm_bindgen]
+#[must_use]
+pub fn validate(key: &str) -> Validation
+{
+  match crate::validate(key) {
+    Ok(x) => Validation { valid:  true,
+                          format: Some(name(x.release)),
+                          error:  None, },
+    Err(e) => Validation { valid:  false,
+                           format: None,
+                           error:  Some(e.to_string()), },
+  }
+}
+
+/// The names of every format `key` is valid for, like
+/// [`identify`](../fn.identify.html)'s `matches`.
+#[wasm_bindgen]
+#[must_use]
+pub fn identify(key: &str) -> Vec<String>
+{
+  crate::identify(key).matches
+                      .iter()
+                      .map(|x| name(x.release))
+                      .collect()
+}
+
+/// Everything wkv can say about `key`, as a JSON object for a demo page to
+/// `JSON.parse`: the key as given and [`normalize`](../fn.normalize.html)d,
+/// whether it's valid and as what, why not and where (from
+/// [`diagnose`](../fn.diagnose.html)), and every format it matches (from
+/// [`identify`](../fn.identify.html)).
+///
+/// This is what [`validate_report`](../fn.validate_report.html) covers, minus
+/// the timing, since there's no clock on `wasm32-unknown-unknown`.
+#[wasm_bindgen]
+#[must_use]
+pub fn check(key: &str) -> String
+{
+  let normalized = crate::normalize(key);
+  let result = crate::diagnose(&normalized);
+  let matches: Vec<_> = crate::identify(&normalized).matches
+                                                    .iter()
+                                                    .map(|x| json::string(&name(x.release)))
+                                                    .collect();
+  let (format, error, offset) = match result {
+    Ok(x) => (Some(x.release), None, None),
+    Err(e) => (e.format, Some(e.to_string()), e.offset),
+  };
+
+  format!("{{\"key\":{},\"normalized\":{},\"valid\":{},\"format\":{},\"error\":{},\
+           \"offset\":{},\"matches\":[{}]}}",
+          json::string(key),
+          json::string(&normalized),
+          error.is_none(),
+          format.map_or_else(|| "null".to_owned(), |x| json::string(&name(x))),
+          error.as_deref().map_or_else(|| "null".to_owned(), json::string),
+          offset.map_or_else(|| "null".to_owned(), |x| x.to_string()),
+          matches.join(","))
+}
+
+/// The name JavaScript sees for `x`.
+fn name(x: KeyType) -> String
+{
+  format!("{x:?}")
+}
+
+#[cfg(test)]
+mod tests
+{
+  use super::*;
+
+  #[test]
+  fn valid()
+  {
+    assert_eq!(validate("757-2573155"),
+               Validation { valid:  true,
+                            format: Some("Windows95".to_owned()),
+                            error:  None, });
+  }
+
+  #[test]
+  fn invalid()
+  {
+    assert_eq!(validate("000-5555554"),
+               Validation { valid:  false,
+                            format: None,
+                            error:  Some("key fails the mod 7 checksum".to_owned()), });
+  }
+
+  #[test]
+  fn demo()
+  {
+    assert_eq!(check("757-2573155"),
+               concat!(r#"{"key":"757-2573155","normalized":"757-2573155","valid":true,"#,
+                       r#""format":"Windows95","error":null,"offset":null,"#,
+                       r#""matches":["Windows95","WindowsNT4"]}"#));
+    assert_eq!(check("000 5555554"),
+               concat!(r#"{"key":"000 5555554","normalized":"000-5555554","valid":false,"#,
+                       r#""format":"Windows95","#,
+                       r#""error":"key fails the mod 7 checksum at offset 4","offset":4,"#,
+                       r#""matches":[]}"#));
+    assert_eq!(check("\"").get(..12), Some(r#"{"key":"\"","#));
+  }
+
+  #[test]
+  fn overlapping()
+  {
+    assert_eq!(identify("757-2573155"), ["Windows95", "WindowsNT4"]);
+  }
+}
@@ -0,0 +1,101 @@
+// This Source Code Form is subject to the terms of the Mozilla Public License,
+// v. 2.0. If a copy of the MPL was not distributed with this file, You can
+// obtain one at https://mozilla.org/MPL/2.0/.
+//! Windows 95 retail keys, `DDD-DDDDDDD`.
+//!
+//! For callers that already know they have a Windows 95 key.
+//! [`validate`](fn.validate.html) checks it like
+//! [`validate_windows95`](../fn.validate_windows95.html), but returns a
+//! [`Windows95Key`](struct.Windows95Key.html) rather than a generic
+//! [`Key`](../struct.Key.html).
+
+use crate::{ascii, mod7, number, Key, KeyParts, KeyType, WKVError};
+
+/// A valid Windows 95 key.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Windows95Key
+{
+  /// The first segment, usually called the site number. Windows 95 allows
+  /// any characters here, so it's kept as bytes.
+  pub site:   [u8; 3],
+  /// The second segment.
+  pub serial: u32,
+}
+
+impl From<Windows95Key> for Key
+{
+  #[inline]
+  fn from(x: Windows95Key) -> Self
+  {
+    Self { release: KeyType::Windows95,
+           parts:   KeyParts::ThreeSeven { site:   x.site,
+                                           serial: x.serial, }, }
+  }
+}
+
+/// Validates a Windows 95 format key. See
+/// [`validate_windows95`](../fn.validate_windows95.html) for the rules.
+///
+/// This is a `const fn`, so [`validate_const!`](../macro.validate_const.html)
+/// can check a key at compile time.
+#[inline]
+pub const fn validate(key: &'_ str) -> Result<Windows95Key, WKVError>
+{
+  let (site, serial) = match ascii(key) {
+    Ok([a, b, c, _, serial @ ..]) if serial.len() == 7 => ([*a, *b, *c], serial),
+    Ok(key) if key.len() < 11 => return Err(WKVError::TooShort),
+    Ok(_) => return Err(WKVError::TooLong),
+    Err(e) => return Err(e),
+  };
+
+  // 333, 444, ..., 999.
+  if site[0] == site[1] && site[1] == site[2] && matches!(site[0], b'3'..=b'9') {
+    return Err(WKVError::InvalidDigitPosition);
+  }
+
+  match mod7(serial) {
+    Ok(true) => (),
+    Ok(false) => return Err(WKVError::BadMod7),
+    Err(e) => return Err(e),
+  }
+
+  match number(serial) {
+    Ok(serial) => Ok(Windows95Key { site, serial }),
+    Err(e) => Err(e),
+  }
+}
+
+#[cfg(test)]
+mod tests
+{
+  use super::*;
+
+  #[test]
+  fn parts()
+  {
+    assert_eq!(validate("757-2573155"),
+               Ok(Windows95Key { site:   *b"757",
+                                 serial: 2_573_155, }));
+    assert_eq!(validate("YOL-1111111").map(|x| x.site), Ok(*b"YOL"));
+  }
+
+  #[test]
+  fn invalid()
+  {
+    assert_eq!(validate("333-1111111"), Err(WKVError::InvalidDigitPosition));
+    assert_eq!(validate("000-5555554"), Err(WKVError::BadMod7));
+  }
+
+  #[test]
+  fn length()
+  {
+    assert_eq!(validate("000-"), Err(WKVError::TooShort));
+    assert_eq!(validate("757-25731550"), Err(WKVError::TooLong));
+  }
+
+  #[test]
+  fn into_key()
+  {
+    assert_eq!(validate("757-2573155").map(Key::from), crate::validate_windows95("757-2573155"));
+  }
+}
@@ -0,0 +1,118 @@
+// This Source Code Form is subject to the terms of the Mozilla Public License,
+// v. 2.0. If a copy of the MPL was not distributed with this file, You can
+// obtain one at https://mozilla.org/MPL/2.0/.
+//! Windows 95 OEM keys, `DDDYY-OEM-00DDDDD-DDDDD`.
+//!
+//! For callers that already know they have a Windows 95 OEM key.
+//! [`validate`](fn.validate.html) checks it like
+//! [`validate_windows95_oem`](../fn.validate_windows95_oem.html), but returns
+//! a [`Windows95OemKey`](struct.Windows95OemKey.html) rather than a generic
+//! [`Key`](../struct.Key.html).
+
+use crate::{ascii, bytes, mod7, number, Key, KeyParts, KeyType, WKVError};
+
+/// A valid Windows 95 OEM key.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Windows95OemKey
+{
+  /// The day of the year the key was issued, from 1 to 366.
+  pub day:    u32,
+  /// The last two digits of the year the key was issued.
+  pub year:   u32,
+  /// The third segment.
+  pub serial: u32,
+  /// The last segment, which isn't checked, so is kept as bytes.
+  pub suffix: [u8; 5],
+}
+
+impl From<Windows95OemKey> for Key
+{
+  #[inline]
+  fn from(x: Windows95OemKey) -> Self
+  {
+    Self { release: KeyType::Windows95OEM,
+           parts:   KeyParts::Oem { day:    x.day,
+                                    year:   x.year,
+                                    serial: x.serial,
+                                    suffix: x.suffix, }, }
+  }
+}
+
+/// Validates a Windows 95 OEM format key. See
+/// [`validate_windows95_oem`](../fn.validate_windows95_oem.html) for the
+/// rules.
+#[inline]
+pub fn validate(key: &'_ str) -> Result<Windows95OemKey, WKVError>
+{
+  let key = ascii(key)?;
+
+  match key.len() {
+    0..=22 => return Err(WKVError::TooShort),
+    23 => (),
+    _ => return Err(WKVError::TooLong),
+  }
+
+  if [5, 9, 17].iter().any(|&i| key.get(i) != Some(&b'-')) {
+    return Err(WKVError::ExpectedSeparator);
+  }
+
+  let day = number(key.get(0..3).ok_or(WKVError::Internal)?)?;
+  let year = number(key.get(3..5).ok_or(WKVError::Internal)?)?;
+  let serial = key.get(10..17).ok_or(WKVError::Internal)?;
+
+  if !(1..=366).contains(&day) || !matches!(year, 0..=3 | 95..=99) {
+    Err(WKVError::InvalidDate)
+  } else if key.get(6..9).ok_or(WKVError::Internal)? != b"OEM" {
+    Err(WKVError::ExpectedOEM)
+  } else if !mod7(serial)? {
+    Err(WKVError::BadMod7)
+  } else if !serial.starts_with(b"00") {
+    Err(WKVError::InvalidDigitPosition)
+  } else {
+    let suffix = key.get(18..23).ok_or(WKVError::Internal)?;
+
+    Ok(Windows95OemKey { day,
+                         year,
+                         serial: number(serial)?,
+                         suffix: bytes(suffix)?, })
+  }
+}
+
+#[cfg(test)]
+mod tests
+{
+  use super::*;
+
+  #[test]
+  fn parts()
+  {
+    assert_eq!(validate("12395-OEM-0000007-12345"),
+               Ok(Windows95OemKey { day:    123,
+                                    year:   95,
+                                    serial: 7,
+                                    suffix: *b"12345", }));
+  }
+
+  #[test]
+  fn invalid()
+  {
+    assert_eq!(validate("00095-OEM-0000007-12345"), Err(WKVError::InvalidDate));
+    assert_eq!(validate("12395-OEN-0000007-12345"), Err(WKVError::ExpectedOEM));
+  }
+
+  #[test]
+  fn shape()
+  {
+    assert_eq!(validate("12395-OEM-0000007-1234"), Err(WKVError::TooShort));
+    assert_eq!(validate("12395-OEM-0000007-12345-EXTRA"), Err(WKVError::TooLong));
+    assert_eq!(validate("12395 OEM 0000007 12345"), Err(WKVError::ExpectedSeparator));
+    assert_eq!(validate("12395-OEM-0000007112345"), Err(WKVError::ExpectedSeparator));
+  }
+
+  #[test]
+  fn into_key()
+  {
+    assert_eq!(validate("12395-OEM-0000007-12345").map(Key::from),
+               crate::validate_windows95_oem("12395-OEM-0000007-12345"));
+  }
+}
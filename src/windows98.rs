@@ -0,0 +1,83 @@
+// This Source Code Form is subject to the terms of the Mozilla Public License,
+// v. 2.0. If a copy of the MPL was not distributed with this file, You can
+// obtain one at https://mozilla.org/MPL/2.0/.
+//! Windows 98 and Windows Me keys, `XXXXX-XXXXX-XXXXX-XXXXX-XXXXX`.
+//!
+//! For callers that already know they have a Windows 98 key.
+//! [`validate`](fn.validate.html) checks it like
+//! [`validate_windows98`](../fn.validate_windows98.html), but returns a
+//! [`Windows98Key`](struct.Windows98Key.html) rather than a generic
+//! [`Key`](../struct.Key.html).
+
+use crate::{decode_base24, Key, KeyParts, KeyType, WKVError};
+
+/// A valid Windows 98 key.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Windows98Key
+{
+  /// The key decoded with [`decode_base24`](../fn.decode_base24.html). It
+  /// fits in 114 bits.
+  pub value: u128,
+}
+
+impl From<Windows98Key> for Key
+{
+  #[inline]
+  fn from(x: Windows98Key) -> Self
+  {
+    Self { release: KeyType::Windows98,
+           parts:   KeyParts::Base24(x.value), }
+  }
+}
+
+/// Validates a Windows 98 format key. See
+/// [`validate_windows98`](../fn.validate_windows98.html) for the rules, and
+/// what isn't checked.
+#[inline]
+pub fn validate(key: &'_ str) -> Result<Windows98Key, WKVError>
+{
+  let value = decode_base24(key)?;
+
+  if value >> 114 == 0 {
+    Ok(Windows98Key { value })
+  } else {
+    Err(WKVError::DecodedTooLarge)
+  }
+}
+
+#[cfg(test)]
+mod tests
+{
+  use super::*;
+
+  #[test]
+  fn parts()
+  {
+    assert_eq!(validate("BBBBB-BBBBB-BBBBB-BBBBB-BBBBC"), Ok(Windows98Key { value: 1 }));
+  }
+
+  #[test]
+  fn invalid()
+  {
+    assert_eq!(validate("99999-99999-99999-99999-99999"), Err(WKVError::DecodedTooLarge));
+    assert_eq!(validate("BBBBB-BBBBB-BBBBB-BBBBB-BBBBA"), Err(WKVError::InvalidCharacter));
+  }
+
+  #[test]
+  fn shape()
+  {
+    assert_eq!(validate("BBBBB-BBBBB-BBBBB-BBBBB-BBBB"), Err(WKVError::TooShort));
+    assert_eq!(validate("BBBBBBBBBBBBBBBBBBBBBBBBBB"), Err(WKVError::TooLong));
+    assert_eq!(validate("-------BBBBBBBBBBBBBBBBBBBBBBBBB"), Err(WKVError::TooLong));
+    assert_eq!(validate("BBBBBBBBBBBBBBBBBBBBBBBBB----"), Err(WKVError::ExpectedSeparator));
+    assert_eq!(validate("B-B-B-B-BBBBBBBBBBBBBBBBBBBBB"), Err(WKVError::ExpectedSeparator));
+    assert_eq!(validate("BBBBBBBBBBBBBBBBBBBBBBBBC"), Ok(Windows98Key { value: 1 }));
+  }
+
+  #[test]
+  fn into_key()
+  {
+    assert_eq!(validate("MXYHTP7QKM4BCR6DW8J2GF9VC").map(Key::from),
+               crate::validate_windows98("MXYHTP7QKM4BCR6DW8J2GF9VC"));
+  }
+}
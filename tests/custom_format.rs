@@ -0,0 +1,79 @@
+// This Source Code Form is subject to the terms of the Mozilla Public License,
+// v. 2.0. If a copy of the MPL was not distributed with this file, You can
+// obtain one at https://mozilla.org/MPL/2.0/.
+//! A [`KeyFormat`](../wkv/trait.KeyFormat.html) implemented outside wkv, as a
+//! downstream crate would, registered alongside the built-in formats.
+#![cfg(feature = "std")]
+
+use std::convert::TryInto;
+
+use wkv::{FormatRegistry, Key, KeyFormat, KeyParts, KeyType, WKVError};
+
+/// A made-up in-house format, `DDD-DDDDDDD-D`: a Windows 95-style key with a
+/// check digit, the last digit of the sum of the others.
+struct CheckDigit;
+
+impl KeyFormat for CheckDigit
+{
+  fn name(&self) -> &str
+  {
+    "check digit"
+  }
+
+  fn key_type(&self) -> KeyType
+  {
+    KeyType::Unknown
+  }
+
+  fn matches_length(&self, key: &str) -> bool
+  {
+    let dash_at = |i| key.as_bytes().get(i) == Some(&b'-');
+
+    key.len() == 13 && dash_at(3) && dash_at(11)
+  }
+
+  fn validate(&self, key: &str) -> Result<Key, WKVError>
+  {
+    let digits: Vec<u32> = key.bytes()
+                              .filter(|&x| x != b'-')
+                              .map(|x| char::from(x).to_digit(10).ok_or(WKVError::ExpectedDigit))
+                              .collect::<Result<_, _>>()?;
+    let (check, rest) = digits.split_last().ok_or(WKVError::TooShort)?;
+
+    if rest.iter().sum::<u32>() % 10 != *check {
+      return Err(WKVError::BadMod7);
+    }
+
+    let site = key.as_bytes()
+                  .get(0..3)
+                  .and_then(|x| x.try_into().ok())
+                  .ok_or(WKVError::TooShort)?;
+    let serial = key.get(4..11)
+                    .and_then(|x| x.parse().ok())
+                    .ok_or(WKVError::ExpectedDigit)?;
+
+    Ok(Key::new(KeyType::Unknown,
+                KeyParts::ThreeSeven { site, serial }))
+  }
+}
+
+#[test]
+fn registered()
+{
+  let mut registry = FormatRegistry::new();
+
+  registry.register(CheckDigit);
+  assert_eq!(registry.validate("123-0000007-3"),
+             Ok(Key::new(KeyType::Unknown,
+                         KeyParts::ThreeSeven { site:   *b"123",
+                                                serial: 7, })));
+  assert_eq!(registry.validate("123-0000007-4"), Err(WKVError::BadMod7));
+  assert_eq!(registry.validate("757-2573155").map(|x| x.release),
+             Ok(KeyType::Windows95));
+  assert_eq!(wkv::validate("123-0000007-3"), Err(WKVError::UnknownFormat));
+
+  let identification = registry.identify("123-0000007-3");
+  assert_eq!(identification.matches.iter().map(|x| x.release).collect::<Vec<_>>(),
+             [KeyType::Unknown]);
+  assert_eq!(registry.formats().last().map(KeyFormat::name), Some("check digit"));
+}
@@ -0,0 +1,73 @@
+// This Source Code Form is subject to the terms of the Mozilla Public License,
+// v. 2.0. If a copy of the MPL was not distributed with this file, You can
+// obtain one at https://mozilla.org/MPL/2.0/.
+//! Property tests that nothing panics, whatever it's given. Keys come from
+//! untrusted input, so a panic is always a bug, as is
+//! [`WKVError::Internal`](../wkv/enum.WKVError.html#variant.Internal).
+#![cfg(feature = "std")]
+
+use proptest::prelude::*;
+use wkv::{Key, WKVError};
+
+/// Fails if `result` is an internal error.
+fn no_internal(result: Result<Key, WKVError>)
+{
+  assert_ne!(result.err(), Some(WKVError::Internal));
+}
+
+/// Every function that takes a key, called on `key`.
+fn check(key: &str)
+{
+  no_internal(wkv::validate(key));
+  no_internal(wkv::validate_windows95(key));
+  no_internal(wkv::validate_windows95_oem(key));
+  no_internal(wkv::validate_windows98(key));
+  no_internal(wkv::validate_windows_nt4(key));
+  no_internal(wkv::validate_office97(key));
+  no_internal(wkv::validate_windows2000(key));
+  no_internal(wkv::validate_windows_xp(key));
+  let _ = wkv::validate_lenient(key);
+  let _ = wkv::validate_with_suggestions(key);
+  assert_ne!(wkv::decode_base24(key).err(), Some(WKVError::Internal));
+  let _ = wkv::mod7(key.as_bytes());
+  let _ = wkv::checksum(key);
+  let _ = wkv::shape_of(key);
+  let _ = wkv::normalize(key);
+  let _ = wkv::epid::parse(key);
+
+  if let Ok(key) = wkv::validate(key) {
+    let _ = key.metrics();
+    let _ = key.to_string();
+  }
+  if let Err(diagnostic) = wkv::diagnose(key) {
+    assert_ne!(diagnostic.error, WKVError::Internal);
+    let _ = diagnostic.to_string();
+  }
+  let identification = wkv::identify(key);
+  assert!(identification.failures.iter().all(|x| x.1 != WKVError::Internal));
+  let _ = identification.closest();
+  let _ = wkv::validate_report(key);
+}
+
+proptest! {
+  #[test]
+  fn any_string(key in any::<String>())
+  {
+    check(&key);
+  }
+
+  // Arbitrary strings almost never have a format's length, so also try ones
+  // that do, made of the characters keys are made of plus some multi-byte
+  // ones.
+  #[test]
+  fn key_like(key in "[0-9A-Zé€😀 -]{10,30}")
+  {
+    check(&key);
+  }
+
+  #[test]
+  fn digital_product_id(blob in proptest::collection::vec(any::<u8>(), 0..200))
+  {
+    let _ = wkv::decode_digital_product_id(&blob);
+  }
+}
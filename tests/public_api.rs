@@ -0,0 +1,294 @@
+// This Source Code Form is subject to the terms of the Mozilla Public License,
+// v. 2.0. If a copy of the MPL was not distributed with this file, You can
+// obtain one at https://mozilla.org/MPL/2.0/.
+//! A snapshot of wkv's public API, as seen by a downstream crate. Changing or
+//! removing anything pinned here is a breaking change (see "Stability" in the
+//! Readme): the crate must not be released with this file edited except to
+//! add items, unless the minor version is bumped.
+//!
+//! Functions are pinned by coercing them to function pointers, enums by
+//! matching every variant and structs by destructuring every field, so
+//! signature changes and removed items fail to compile, as do new variants
+//! and fields on exhaustive types. `#[non_exhaustive]` types need a `_` arm
+//! or `..`, as they do downstream.
+#![cfg(feature = "std")]
+
+use wkv::{analysis::{self, CorpusAnalysis},
+          epid::{self, ExtendedPid},
+          windows95::{self, Windows95Key},
+          windows95_oem::{self, Windows95OemKey},
+          windows98::{self, Windows98Key},
+          BatchStatistics,
+          CharClass,
+          Checksum,
+          Confidence,
+          Exclusion,
+          Diagnostic,
+          ExclusionReason,
+          FormatRegistry,
+          GenerationPolicy,
+          Identification,
+          Key,
+          KeyClass,
+          KeyFormat,
+          KeyMetrics,
+          KeyParts,
+          KeyShape,
+          KeyType,
+          KeyTypeInfo,
+          MatchStage,
+          RedactionStyle,
+          Report,
+          Strictness,
+          Suggestion,
+          ValidationOptions,
+          WKVError};
+
+#[test]
+fn functions()
+{
+  let _: fn(&str) -> Result<Key, WKVError> = wkv::validate;
+  let _: fn(&str, &ValidationOptions) -> Result<Key, WKVError> =
+    wkv::validate_with;
+  let _: fn(&str) -> Result<Key, WKVError> = wkv::validate_windows95;
+  let _: fn(&str) -> Result<Key, WKVError> = wkv::validate_windows95_oem;
+  let _: fn(&str) -> Result<Key, WKVError> = wkv::validate_windows98;
+  let _: fn(&str) -> Result<Key, WKVError> = wkv::validate_windows_nt4;
+  let _: fn(&str) -> Result<Key, WKVError> = wkv::validate_office97;
+  let _: fn(&str) -> Result<Key, WKVError> = wkv::validate_windows2000;
+  let _: fn(&str) -> Result<Key, WKVError> = wkv::validate_windows_xp;
+  let _: fn(&str) -> Result<Windows95Key, WKVError> = windows95::validate;
+  let _: fn(&str) -> Result<Windows95OemKey, WKVError> = windows95_oem::validate;
+  let _: fn(&str) -> Result<Windows98Key, WKVError> = windows98::validate;
+  let _: fn(Windows95Key) -> Key = Key::from;
+  let _: fn(Windows95OemKey) -> Key = Key::from;
+  let _: fn(Windows98Key) -> Key = Key::from;
+  let _: fn(&str) -> Result<u128, WKVError> = wkv::decode_base24;
+  let _: fn(&[u8]) -> Result<String, WKVError> = wkv::decode_digital_product_id;
+  let _: &[u8; 24] = wkv::BASE24_ALPHABET;
+  let _: fn(&[u8]) -> Result<bool, WKVError> = wkv::mod7;
+  let _: fn(&str) -> Option<Checksum> = wkv::checksum;
+  let _: &str = wkv::validate_const!("757-2573155");
+  let _: fn(&str) -> Option<KeyShape> = wkv::shape_of;
+  let _: fn(&str) -> Identification = wkv::identify;
+  let _: fn(u16) -> Vec<KeyType> = wkv::formats_in_use;
+  let _: fn(&str) -> Result<Key, Diagnostic> = wkv::diagnose;
+  let _: fn(&str) -> String = wkv::normalize;
+  let _: fn(&str) -> Report = wkv::validate_report;
+  let _: fn(&Identification) -> Vec<(KeyType, MatchStage)> = Identification::stages;
+  let _: fn(&Identification) -> Option<(KeyType, MatchStage)> = Identification::closest;
+  let _: Result<Key, (WKVError, Vec<Suggestion>)> = wkv::validate_with_suggestions("");
+  let _: fn() -> FormatRegistry = FormatRegistry::new;
+  let _: fn() -> FormatRegistry = FormatRegistry::empty;
+  let _: fn(&mut FormatRegistry, KeyType) = FormatRegistry::register;
+  let _: Vec<&dyn KeyFormat> = FormatRegistry::new().formats().collect();
+  let _: fn(&FormatRegistry, &str) -> Result<Key, WKVError> = FormatRegistry::validate;
+  let _: fn(&FormatRegistry, &str) -> Identification = FormatRegistry::identify;
+  let _: fn(&KeyType) -> &str = <KeyType as KeyFormat>::name;
+  let _: fn(&KeyType) -> KeyType = <KeyType as KeyFormat>::key_type;
+  let _: fn(&KeyType, &str) -> bool = <KeyType as KeyFormat>::matches_length;
+  let _: fn(&KeyType, &str) -> Result<Key, WKVError> = <KeyType as KeyFormat>::validate;
+  let _: fn(&mut BatchStatistics, &Result<Key, WKVError>) = BatchStatistics::record;
+  let _: fn(BatchStatistics, BatchStatistics) -> BatchStatistics = BatchStatistics::merge;
+  let _: Vec<(usize, Result<Key, WKVError>)> = wkv::validate_batch(&[""]).collect();
+  let _: Vec<std::io::Result<(usize, Result<Key, WKVError>)>> =
+    wkv::validate_lines(&b""[..]).collect();
+  let _: fn(&str) -> Result<(Key, String), WKVError> = wkv::validate_lenient;
+  let _: fn(u8) -> CharClass = CharClass::from_layout;
+  let _: fn(CharClass, u8) -> bool = CharClass::matches;
+  let _: fn(CharClass, u8) -> Option<u8> = CharClass::substitute;
+  let _: fn(&str) -> Result<ExtendedPid, WKVError> = epid::parse;
+  let _: fn(&[Key]) -> Option<CorpusAnalysis> = analysis::analyze;
+  let _: fn(KeyType) -> &'static KeyTypeInfo = KeyType::info;
+  let _: fn(KeyType) -> GenerationPolicy = KeyType::generation_policy;
+  let _: fn(KeyType) -> Confidence = KeyType::implementation_confidence;
+  let _: fn(KeyType) -> bool = KeyType::signature_unchecked;
+  let _: fn(KeyType, usize) -> Option<CharClass> = KeyType::allowed_at;
+  let _: fn(KeyType) -> &'static str = KeyType::name;
+  let _: fn(KeyType) -> Option<u16> = KeyType::release_year;
+  let _: fn(KeyType) -> &'static str = KeyType::pattern;
+  let _: fn(KeyType) -> &'static str = KeyType::example;
+  let _: fn(KeyType, KeyParts) -> Key = Key::new;
+  let _: fn(&Key) -> Option<KeyClass> = Key::class;
+  let _: fn(&Key) -> Option<KeyMetrics> = Key::metrics;
+  let _: fn(&Key) -> String = Key::canonical;
+  let _: fn(&Key) -> String = Key::redacted;
+  let _: fn(&str, RedactionStyle) -> String = wkv::redact;
+}
+
+#[test]
+fn structs()
+{
+  let _ = |x: Key| {
+    let Key { release: _,
+              parts: _,
+              .. } = x;
+  };
+  let ValidationOptions { min_confidence: _,
+                          strictness: _, } = ValidationOptions::default();
+  let KeyTypeInfo { key_type: _,
+                    name: _,
+                    release_year: _,
+                    generation_policy: _,
+                    confidence: _,
+                    signature_unchecked: _,
+                    class: _,
+                    shape: _,
+                    layout: _,
+                    pattern: _,
+                    example: _,
+                    .. } = KeyType::Unknown.info();
+  let _ = |x: CorpusAnalysis| {
+    let CorpusAnalysis { keys: _,
+                         digit_chi_squared: _,
+                         top_prefix: _,
+                         likely_generated: _, } = x;
+  };
+  let _ = |x: KeyMetrics| {
+    let KeyMetrics { digit_counts: _,
+                     longest_run: _,
+                     digit_sum: _,
+                     checksum_slack: _,
+                     .. } = x;
+  };
+  let BatchStatistics { by_type: _,
+                        by_error: _, } = BatchStatistics::default();
+  let _ = |x: Diagnostic| {
+    let Diagnostic { error: _,
+                     format: _,
+                     offset: _,
+                     expected: _,
+                     substitution: _, } = x;
+  };
+  let Checksum { offset: _,
+                 sum: _,
+                 remainder: _, } = Checksum { offset:    0,
+                                               sum:       0,
+                                               remainder: 0, };
+  let Suggestion { offset: _,
+                   replacement: _, } = Suggestion { offset:      0,
+                                                    replacement: b'0', };
+  let Report { input: _,
+               normalized: _,
+               result: _,
+               identification: _,
+               elapsed: _, } = wkv::validate_report("");
+  let Identification { matches: _,
+                       failures: _,
+                       excluded: _, } = wkv::identify("");
+  let _ = |x: Exclusion| {
+    let Exclusion { release: _,
+                    reason: _, } = x;
+  };
+  let Windows95Key { site: _,
+                     serial: _, } = Windows95Key { site:   *b"000",
+                                                   serial: 0, };
+  let _ = |x: Windows95OemKey| {
+    let Windows95OemKey { day: _,
+                          year: _,
+                          serial: _,
+                          suffix: _, } = x;
+  };
+  let Windows98Key { value: _ } = Windows98Key { value: 0 };
+  let _ = |pid: ExtendedPid| {
+    let ExtendedPid { platform: _,
+                      group: _,
+                      channel_id: _,
+                      sequence: _,
+                      license_channel: _,
+                      language: _,
+                      build: _,
+                      build_minor: _,
+                      activation_day: _,
+                      activation_year: _, } = pid;
+  };
+}
+
+#[test]
+#[allow(deprecated)]
+fn enums()
+{
+  let _ = |x: WKVError| match x {
+    WKVError::TooShort
+    | WKVError::TooLong
+    | WKVError::BadMod7
+    | WKVError::ExpectedDigit
+    | WKVError::InvalidDigitPosition
+    | WKVError::ExpectedSeparator
+    | WKVError::ExpectedOEM
+    | WKVError::InvalidCharacter
+    | WKVError::DecodedTooLarge
+    | WKVError::InvalidDate
+    | WKVError::BadAccess
+    | WKVError::InsufficientConfidence
+    | WKVError::QuirkOnlyValid
+    | WKVError::NonAscii
+    | WKVError::Internal
+    | WKVError::UnknownFormat => (),
+    _ => (),
+  };
+  let _ = |x: KeyType| match x {
+    KeyType::Windows95
+    | KeyType::Windows95OEM
+    | KeyType::Windows98
+    | KeyType::WindowsNT4
+    | KeyType::Office97
+    | KeyType::Windows2000
+    | KeyType::WindowsXP
+    | KeyType::Unknown => (),
+    _ => (),
+  };
+  let _ = |x: KeyClass| match x {
+    KeyClass::Retail
+    | KeyClass::OemPreinstall
+    | KeyClass::Volume
+    | KeyClass::Evaluation
+    | KeyClass::Checked => (),
+  };
+  let _ = |x: ExclusionReason| match x {
+    ExclusionReason::WrongShape | ExclusionReason::WrongCharacters(_) => (),
+  };
+  let _ = |x: KeyParts| match x {
+    KeyParts::ThreeSeven { site: _,
+                           serial: _, }
+    | KeyParts::FourSeven { site: _,
+                            serial: _, }
+    | KeyParts::Oem { day: _,
+                      year: _,
+                      serial: _,
+                      suffix: _, }
+    | KeyParts::Base24(_) => (),
+    _ => (),
+  };
+  let _ = |x: CharClass| match x {
+    CharClass::Digit
+    | CharClass::Base24
+    | CharClass::Separator
+    | CharClass::Exactly(_)
+    | CharClass::Any => (),
+  };
+  let _ = |x: KeyShape| match x {
+    KeyShape::ThreeSeven | KeyShape::FourSeven | KeyShape::PidLike | KeyShape::FiveByFive => (),
+  };
+  let _ = |x: Confidence| match x {
+    Confidence::Heuristic
+    | Confidence::CommunityDocumented
+    | Confidence::VerifiedFromDecompilation => (),
+  };
+  let _ = |x: MatchStage| match x {
+    MatchStage::WrongShape
+    | MatchStage::WrongCharacters
+    | MatchStage::WrongStructure
+    | MatchStage::BadChecksum
+    | MatchStage::Valid => (),
+  };
+  let _ = |x: RedactionStyle| match x {
+    RedactionStyle::Full | RedactionStyle::KeepFirstSegment | RedactionStyle::KeepLastSegment => (),
+  };
+  let _ = |x: Strictness| match x {
+    Strictness::Quirks | Strictness::Documented => (),
+  };
+  let _ = |x: GenerationPolicy| match x {
+    GenerationPolicy::Allowed | GenerationPolicy::TestOnly | GenerationPolicy::Refused => (),
+  };
+}
@@ -0,0 +1,44 @@
+// This Source Code Form is subject to the terms of the Mozilla Public License,
+// v. 2.0. If a copy of the MPL was not distributed with this file, You can
+// obtain one at https://mozilla.org/MPL/2.0/.
+//! Keeps the WebAssembly build small enough to embed in a demo page. This
+//! builds the crate again, so it only runs when asked for:
+//! `cargo test --features wasm --test wasm_size -- --ignored`. It needs the
+//! `wasm32-unknown-unknown` target installed.
+#![cfg(feature = "wasm")]
+
+use std::process::Command;
+
+/// The most the release build of the `wasm` feature may weigh, in bytes.
+const BUDGET: u64 = 128 * 1024;
+
+#[test]
+#[ignore = "builds the crate for wasm32-unknown-unknown"]
+fn under_budget()
+{
+  // A separate target directory, since this one is locked while tests run.
+  let target_dir = concat!(env!("CARGO_TARGET_TMPDIR"), "/wasm_size");
+  let status = Command::new(env!("CARGO")).args(["rustc",
+                                                 "--quiet",
+                                                 "--lib",
+                                                 "--release",
+                                                 "--target",
+                                                 "wasm32-unknown-unknown",
+                                                 "--features",
+                                                 "wasm",
+                                                 "--crate-type",
+                                                 "cdylib",
+                                                 "--target-dir",
+                                                 target_dir])
+                                          .current_dir(env!("CARGO_MANIFEST_DIR"))
+                                          .status()
+                                          .map(|x| x.success());
+
+  assert_eq!(status.ok(), Some(true), "the wasm build failed");
+
+  let size = std::fs::metadata(format!("{target_dir}/wasm32-unknown-unknown/release/wkv.wasm"))
+             .map(|x| x.len())
+             .unwrap_or(u64::MAX);
+
+  assert!(size <= BUDGET, "wkv.wasm is {} bytes, over the budget of {}", size, BUDGET);
+}